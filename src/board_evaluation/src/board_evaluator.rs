@@ -1,9 +1,10 @@
 use crate::evaluation_constants::{BISHOP_WEIGHT, BLOCKED_PAWN_WEIGHT, DOUBLED_PAWN_WEIGHT, ISOLATED_PAWN_WEIGHT, KING_WEIGHT, KNIGHT_WEIGHT, PAWN_WEIGHT, QUEEN_WEIGHT, ROOK_WEIGHT};
 use board_representation::chess_board::ChessBoard;
-use board_representation::chess_board_utils::FILE_MASK;
+use board_representation::square::File;
 
 pub type BoardScore = f32;
 
+#[derive(Clone, Copy)]
 pub struct BoardEvaluator {
     pub eval_material: bool,
     pub eval_doubled_pawns: bool,
@@ -31,18 +32,18 @@ impl BoardEvaluator {
 }
 
 fn eval_material(chess_board: &ChessBoard) -> BoardScore {
-    let king_count = (chess_board.white_pieces & chess_board.kings).count_ones() as f32 -
-        (chess_board.black_pieces & chess_board.kings).count_ones() as f32;
-    let queen_count = (chess_board.white_pieces & chess_board.queens).count_ones() as f32 -
-        (chess_board.black_pieces & chess_board.queens).count_ones() as f32;
-    let rook_count = (chess_board.white_pieces & chess_board.rooks).count_ones() as f32 -
-        (chess_board.black_pieces & chess_board.rooks).count_ones() as f32;
-    let bishop_count = (chess_board.white_pieces & chess_board.bishops).count_ones() as f32 -
-        (chess_board.black_pieces & chess_board.bishops).count_ones() as f32;
-    let knight_count = (chess_board.white_pieces & chess_board.knights).count_ones() as f32 -
-        (chess_board.black_pieces & chess_board.knights).count_ones() as f32;
-    let pawn_count = (chess_board.white_pieces & chess_board.pawns).count_ones() as f32 -
-        (chess_board.black_pieces & chess_board.pawns).count_ones() as f32;
+    let king_count = (chess_board.white_pieces & chess_board.kings).count() as f32 -
+        (chess_board.black_pieces & chess_board.kings).count() as f32;
+    let queen_count = (chess_board.white_pieces & chess_board.queens).count() as f32 -
+        (chess_board.black_pieces & chess_board.queens).count() as f32;
+    let rook_count = (chess_board.white_pieces & chess_board.rooks).count() as f32 -
+        (chess_board.black_pieces & chess_board.rooks).count() as f32;
+    let bishop_count = (chess_board.white_pieces & chess_board.bishops).count() as f32 -
+        (chess_board.black_pieces & chess_board.bishops).count() as f32;
+    let knight_count = (chess_board.white_pieces & chess_board.knights).count() as f32 -
+        (chess_board.black_pieces & chess_board.knights).count() as f32;
+    let pawn_count = (chess_board.white_pieces & chess_board.pawns).count() as f32 -
+        (chess_board.black_pieces & chess_board.pawns).count() as f32;
 
     KING_WEIGHT * king_count +
         QUEEN_WEIGHT * queen_count +
@@ -55,9 +56,9 @@ fn eval_material(chess_board: &ChessBoard) -> BoardScore {
 fn eval_doubled_pawns(chess_board: &ChessBoard) -> BoardScore {
     let mut doubled_pawns: i32 = 0;
     for i in 0..8 {
-        let pawns_on_file = chess_board.pawns & FILE_MASK[i];
-        let white_pawns = (pawns_on_file & chess_board.white_pieces).count_ones() as i32;
-        let black_pawns = (pawns_on_file & chess_board.black_pieces).count_ones() as i32;
+        let pawns_on_file = chess_board.pawns & File::ALL[i].bitboard();
+        let white_pawns = (pawns_on_file & chess_board.white_pieces).count() as i32;
+        let black_pawns = (pawns_on_file & chess_board.black_pieces).count() as i32;
         if white_pawns > 1 {
             doubled_pawns += white_pawns
         }
@@ -73,9 +74,9 @@ fn eval_isolated_pawns(chess_board: &ChessBoard) -> BoardScore {
     let mut white_pawns_on_file: [i32; 8] = [0; 8];
     let mut black_pawns_on_file: [i32; 8] = [0; 8];
     for file in 0..8 {
-        let pawns_on_file = chess_board.pawns & FILE_MASK[file];
-        white_pawns_on_file[file] = (pawns_on_file & chess_board.white_pieces).count_ones() as i32;
-        black_pawns_on_file[file] = (pawns_on_file & chess_board.black_pieces).count_ones() as i32;
+        let pawns_on_file = chess_board.pawns & File::ALL[file].bitboard();
+        white_pawns_on_file[file] = (pawns_on_file & chess_board.white_pieces).count() as i32;
+        black_pawns_on_file[file] = (pawns_on_file & chess_board.black_pieces).count() as i32;
     }
     for file in 0..8 {
         if white_pawns_on_file[file] > 0 {
@@ -106,12 +107,12 @@ fn eval_blocked_pawns(chess_board: &ChessBoard) -> BoardScore {
     let white_pawns = chess_board.pawns & chess_board.white_pieces;
     let white_pawn_pushes = white_pawns << 8;
     let white_blockers = white_pawn_pushes & occupied;
-    blocked_pawns += white_blockers.count_ones() as i32;
+    blocked_pawns += white_blockers.count() as i32;
 
     let black_pawns = chess_board.pawns & chess_board.black_pieces;
     let black_pawn_pushes = black_pawns >> 8;
     let black_blockers = black_pawn_pushes & occupied;
-    blocked_pawns -= black_blockers.count_ones() as i32;
+    blocked_pawns -= black_blockers.count() as i32;
 
     BLOCKED_PAWN_WEIGHT * blocked_pawns as f32
 }
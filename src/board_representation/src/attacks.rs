@@ -0,0 +1,288 @@
+//! Attack bitboards of every piece kind.
+//!
+//! Knights, kings and pawns attack a fixed set of squares, stored in tables generated at
+//! compile time. Sliders are looked up in "fancy" magic bitboard tables: the squares that can
+//! block a slider from a square (its relevant occupancy) are multiplied by a magic number, and
+//! the top bits of the product index the attacks for that occupancy.
+//!
+//! The tables are built from raw `u64` bitboards indexed by square index, the public functions
+//! wrap them in [`Bitboard`].
+use crate::bitboard::Bitboard;
+use crate::chess_piece::Color;
+use crate::square::Square;
+use std::sync::LazyLock;
+
+type Direction = (i8, i8);
+
+const KNIGHT_DIRECTIONS: [Direction; 8] = [
+    (1, 2),
+    (2, 1),
+    (-1, 2),
+    (2, -1),
+    (1, -2),
+    (-2, 1),
+    (-1, -2),
+    (-2, -1),
+];
+const KING_DIRECTIONS: [Direction; 8] = [
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, 1),
+    (0, -1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+const WHITE_PAWN_DIRECTIONS: [Direction; 2] = [(1, 1), (1, -1)];
+const BLACK_PAWN_DIRECTIONS: [Direction; 2] = [(-1, 1), (-1, -1)];
+
+const ROOK_DIRECTIONS: [Direction; 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const BISHOP_DIRECTIONS: [Direction; 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// Found offline with a fixed-seed random search, one per square.
+#[rustfmt::skip]
+const ROOK_MAGICS: [u64; 64] = [
+    0x2080_0020_8040_0010, 0x00C0_0020_0140_1000, 0x2100_1100_0840_2002, 0x0880_0800_8104_1000,
+    0x0200_0200_2004_1008, 0x2300_0400_0801_0012, 0x0C00_2830_0400_8201, 0x0180_0100_0040_7A80,
+    0x0168_8000_8040_0020, 0x0010_4000_4020_1000, 0x1001_0020_0100_1048, 0x1001_0024_0810_0100,
+    0x0801_0004_0801_0012, 0x4001_0002_0900_0400, 0x08A2_0004_C802_0001, 0x2002_8011_4500_2280,
+    0x0080_8600_2100_4200, 0x0010_00C0_0940_2002, 0x00B0_0020_0400_2800, 0x100A_8080_1002_0800,
+    0x8101_0100_0800_0410, 0x0244_0080_0200_0480, 0x0000_0400_1081_0208, 0x2000_0200_0044_8534,
+    0x4104_4004_8000_8033, 0x0000_8101_0020_4000, 0x0440_4309_0020_0010, 0x4600_2409_0010_0100,
+    0x0060_0800_8004_0080, 0x0001_0003_0008_0400, 0x0004_0844_0001_1002, 0x0023_0402_0000_8041,
+    0x0580_0500_4300_2080, 0x0400_8040_0280_2008, 0x0001_0020_0100_4010, 0x1000_2009_0100_1000,
+    0x4410_8008_0180_0C00, 0xA012_0038_0600_1004, 0x0020_1001_0400_8802, 0x0004_8084_0200_0041,
+    0x0010_4001_7089_8000, 0x0080_5000_2000_4004, 0x1040_4080_1202_0020, 0x8010_0400_0800_4040,
+    0x2001_0801_0011_0004, 0x0000_0200_0400_8080, 0x0021_0108_1004_0002, 0x0800_008C_4302_0024,
+    0x0000_8000_2100_5100, 0x0070_2010_4000_8080, 0x0000_D042_8200_6A00, 0x0010_0144_0008_0240,
+    0x0001_0801_1005_0100, 0x0012_0008_1024_0600, 0x0402_0008_0104_0200, 0x0281_0010_8A00_4100,
+    0x0050_8003_0010_2045, 0x8208_2100_4012_0882, 0x8010_6001_0118_3441, 0x020B_0009_1000_6045,
+    0x0241_0010_0248_0005, 0x0081_0004_0088_0241, 0x0000_0090_0802_4124, 0x0048_1229_8041_0402,
+];
+
+#[rustfmt::skip]
+const BISHOP_MAGICS: [u64; 64] = [
+    0x0848_0208_2204_0013, 0x8010_A400_8582_1200, 0x0008_0084_3084_0822, 0x0808_0481_0804_0000,
+    0x1304_0421_0000_8104, 0x5001_0120_1020_4023, 0x8104_8801_B820_0420, 0x200A_0080_8401_2000,
+    0x0040_1020_0104_2084, 0x840A_5050_4242_8020, 0x0000_7001_0220_2920, 0x4410_1C0C_1080_0002,
+    0x0040_0404_2200_0000, 0x0180_0208_0209_0202, 0x4020_0208_1104_1202, 0x0001_0430_8C04_2000,
+    0x4140_6610_0242_4400, 0x0028_0120_0801_0460, 0x0188_0621_0200_2A00, 0x0014_0048_4010_2008,
+    0x0105_0002_9040_0002, 0x8001_0222_0041_0400, 0x104A_0419_1801_3446, 0x008A_0000_8200_8238,
+    0x04A0_0600_0810_0430, 0x0008_2200_0882_0801, 0x2508_0412_0800_5010, 0x4008_0802_0020_2020,
+    0x2441_0010_1300_4000, 0x0030_0080_6040_7000, 0x4008_1080_0042_0800, 0x0012_0210_5029_0100,
+    0x0210_0804_8220_0500, 0xCC01_1120_4810_0480, 0x0020_4028_0650_0440, 0x0004_8E00_8058_0080,
+    0x0040_1020_2002_0080, 0x0028_0104_4008_0807, 0x4601_0411_0800_8800, 0x8040_810E_0410_4200,
+    0x9012_1011_0400_088A, 0xA003_0802_1208_1050, 0x00C1_0040_4840_1004, 0x9000_00A0_1440_0800,
+    0x0008_0210_4040_5401, 0x4020_0082_0600_2090, 0x0004_1904_2403_0100, 0x0424_008A_0202_6250,
+    0x8004_0882_5090_0040, 0x1C00_4300_88A0_4200, 0x0001_0200_9404_0001, 0x8040_2100_2088_0061,
+    0x2010_0404_5044_2032, 0x0800_8408_5004_4001, 0x0004_0408_0214_0004, 0x0004_080A_0422_2020,
+    0x8088_8021_1002_2000, 0x1081_A104_1611_4400, 0x0205_010A_2406_0820, 0x0000_0007_2041_1080,
+    0x1008_0002_0843_0400, 0x580C_0260_2881_0840, 0x8020_2044_1020_A110, 0x12C0_0224_0102_0018,
+];
+
+const fn leaper_attacks(directions: &[Direction]) -> [u64; 64] {
+    let mut attacks = [0; 64];
+    let mut index = 0;
+    while index < 64 {
+        let (rank, file) = (index as i8 / 8, index as i8 % 8);
+        let mut i = 0;
+        while i < directions.len() {
+            let (r, f) = (rank + directions[i].0, file + directions[i].1);
+            if r >= 0 && r < 8 && f >= 0 && f < 8 {
+                attacks[index] |= 1 << (r * 8 + f);
+            }
+            i += 1;
+        }
+        index += 1;
+    }
+    attacks
+}
+
+static KNIGHT_ATTACKS: [u64; 64] = leaper_attacks(&KNIGHT_DIRECTIONS);
+static KING_ATTACKS: [u64; 64] = leaper_attacks(&KING_DIRECTIONS);
+static WHITE_PAWN_ATTACKS: [u64; 64] = leaper_attacks(&WHITE_PAWN_DIRECTIONS);
+static BLACK_PAWN_ATTACKS: [u64; 64] = leaper_attacks(&BLACK_PAWN_DIRECTIONS);
+
+/// `BETWEEN[a][b]` and `LINE[a][b]`, both empty when the squares are not on the same rank,
+/// file or diagonal.
+struct AlignedSquares {
+    between: [[u64; 64]; 64],
+    line: [[u64; 64]; 64],
+}
+
+const fn aligned_squares() -> AlignedSquares {
+    let mut between = [[0; 64]; 64];
+    let mut line = [[0; 64]; 64];
+    let mut from = 0;
+    while from < 64 {
+        let (rank, file) = (from as i8 / 8, from as i8 % 8);
+        let mut i = 0;
+        while i < KING_DIRECTIONS.len() {
+            let (rank_delta, file_delta) = KING_DIRECTIONS[i];
+            let full_line = (1 << from)
+                | ray(rank, file, rank_delta, file_delta)
+                | ray(rank, file, -rank_delta, -file_delta);
+            let mut squares_between = 0;
+            let (mut r, mut f) = (rank + rank_delta, file + file_delta);
+            while r >= 0 && r < 8 && f >= 0 && f < 8 {
+                let to = (r * 8 + f) as usize;
+                between[from][to] = squares_between;
+                line[from][to] = full_line;
+                squares_between |= 1 << to;
+                r += rank_delta;
+                f += file_delta;
+            }
+            i += 1;
+        }
+        from += 1;
+    }
+    AlignedSquares { between, line }
+}
+
+/// Squares from (`rank`, `file`), excluded, to the edge of the board.
+const fn ray(rank: i8, file: i8, rank_delta: i8, file_delta: i8) -> u64 {
+    let mut squares = 0;
+    let (mut r, mut f) = (rank + rank_delta, file + file_delta);
+    while r >= 0 && r < 8 && f >= 0 && f < 8 {
+        squares |= 1 << (r * 8 + f);
+        r += rank_delta;
+        f += file_delta;
+    }
+    squares
+}
+
+static ALIGNED_SQUARES: AlignedSquares = aligned_squares();
+
+#[derive(Clone, Copy, Default)]
+struct Magic {
+    /// Squares whose occupancy changes the attacks, the edges of the rays are left out.
+    mask: u64,
+    magic: u64,
+    shift: u32,
+    /// Start of the attacks of this square in the shared table.
+    offset: usize,
+}
+
+impl Magic {
+    fn index(&self, occupancy: u64) -> usize {
+        self.offset + ((occupancy & self.mask).wrapping_mul(self.magic) >> self.shift) as usize
+    }
+}
+
+struct SliderAttacks {
+    rook_magics: [Magic; 64],
+    bishop_magics: [Magic; 64],
+    table: Vec<u64>,
+}
+
+static SLIDER_ATTACKS: LazyLock<SliderAttacks> = LazyLock::new(|| {
+    let mut table = Vec::new();
+    let rook_magics = init_magics(&ROOK_DIRECTIONS, &ROOK_MAGICS, &mut table);
+    let bishop_magics = init_magics(&BISHOP_DIRECTIONS, &BISHOP_MAGICS, &mut table);
+    SliderAttacks {
+        rook_magics,
+        bishop_magics,
+        table,
+    }
+});
+
+/// Walks each ray from `index` until the edge of the board or the first occupied square.
+/// With `skip_edges`, the last square of each ray is left out.
+fn ray_attacks(index: u8, occupancy: u64, directions: &[Direction], skip_edges: bool) -> u64 {
+    let mut attacks = 0;
+    let (rank, file) = ((index / 8) as i8, (index % 8) as i8);
+    for (rank_delta, file_delta) in directions {
+        let (mut r, mut f) = (rank + rank_delta, file + file_delta);
+        let within_bounds = |r: i8, f: i8| (0..8).contains(&r) && (0..8).contains(&f);
+        while within_bounds(r, f) {
+            if skip_edges && !within_bounds(r + rank_delta, f + file_delta) {
+                break;
+            }
+            let square: u64 = 1 << (r * 8 + f);
+            attacks |= square;
+            if occupancy & square != 0 {
+                break;
+            }
+            r += rank_delta;
+            f += file_delta;
+        }
+    }
+    attacks
+}
+
+fn init_magics(directions: &[Direction], magics: &[u64; 64], table: &mut Vec<u64>) -> [Magic; 64] {
+    let mut result = [Magic::default(); 64];
+    for index in 0..64 {
+        let mask = ray_attacks(index, 0, directions, true);
+        let magic = Magic {
+            mask,
+            magic: magics[index as usize],
+            shift: 64 - mask.count_ones(),
+            offset: table.len(),
+        };
+        table.resize(table.len() + (1 << mask.count_ones()), 0);
+
+        // Enumerates all the subsets of the mask (Carry-Rippler trick).
+        let mut occupancy: u64 = 0;
+        loop {
+            let attacks = ray_attacks(index, occupancy, directions, false);
+            let entry = &mut table[magic.index(occupancy)];
+            debug_assert!(
+                *entry == 0 || *entry == attacks,
+                "Bad magic for square {}",
+                index
+            );
+            *entry = attacks;
+            occupancy = occupancy.wrapping_sub(mask) & mask;
+            if occupancy == 0 {
+                break;
+            }
+        }
+        result[index as usize] = magic;
+    }
+    result
+}
+
+pub fn knight_attacks(square: Square) -> Bitboard {
+    Bitboard(KNIGHT_ATTACKS[square.index() as usize])
+}
+
+pub fn king_attacks(square: Square) -> Bitboard {
+    Bitboard(KING_ATTACKS[square.index() as usize])
+}
+
+/// Squares a pawn of `color` on `square` attacks, whether or not there is something to capture.
+pub fn pawn_attacks(square: Square, color: Color) -> Bitboard {
+    match color {
+        Color::White => Bitboard(WHITE_PAWN_ATTACKS[square.index() as usize]),
+        Color::Black => Bitboard(BLACK_PAWN_ATTACKS[square.index() as usize]),
+    }
+}
+
+/// Squares strictly between `from` and `to` if they are aligned, empty otherwise.
+pub fn between(from: Square, to: Square) -> Bitboard {
+    Bitboard(ALIGNED_SQUARES.between[from.index() as usize][to.index() as usize])
+}
+
+/// The whole rank, file or diagonal through `from` and `to` if they are aligned,
+/// empty otherwise.
+pub fn line(from: Square, to: Square) -> Bitboard {
+    Bitboard(ALIGNED_SQUARES.line[from.index() as usize][to.index() as usize])
+}
+
+/// Squares attacked by a rook on `square`, including the first blocker of each ray.
+pub fn rook_attacks(square: Square, occupancy: Bitboard) -> Bitboard {
+    let attacks = &*SLIDER_ATTACKS;
+    Bitboard(attacks.table[attacks.rook_magics[square.index() as usize].index(occupancy.0)])
+}
+
+/// Squares attacked by a bishop on `square`, including the first blocker of each ray.
+pub fn bishop_attacks(square: Square, occupancy: Bitboard) -> Bitboard {
+    let attacks = &*SLIDER_ATTACKS;
+    Bitboard(attacks.table[attacks.bishop_magics[square.index() as usize].index(occupancy.0)])
+}
+
+pub fn queen_attacks(square: Square, occupancy: Bitboard) -> Bitboard {
+    rook_attacks(square, occupancy) | bishop_attacks(square, occupancy)
+}
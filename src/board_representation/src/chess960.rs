@@ -0,0 +1,71 @@
+use crate::chess_board::{CastlingSide, ChessBoard, SquareContent};
+use crate::chess_piece::{Color, Piece, PieceKind};
+use crate::square::{File, Rank, Square};
+
+/// The two knights among the five squares left once the bishops and the queen are placed.
+const KNIGHT_PLACEMENTS: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+/// Puts `kind` on the `empty_index`-th empty square of `back_rank`, from the a-file.
+fn place(back_rank: &mut [Option<PieceKind>; 8], empty_index: usize, kind: PieceKind) {
+    let file = (0..8)
+        .filter(|file| back_rank[*file].is_none())
+        .nth(empty_index)
+        .expect("Not enough empty squares on the back rank");
+    back_rank[file] = Some(kind);
+}
+
+impl ChessBoard {
+    /// The Chess960 starting position `index`, from 0 to 959, in Scharnagl's numbering where
+    /// 518 is the standard starting position. `None` for larger indexes.
+    pub fn chess960(index: u16) -> Option<ChessBoard> {
+        if index >= 960 {
+            return None;
+        }
+
+        let mut back_rank = [None; 8];
+        let mut n = index as usize;
+        back_rank[2 * (n % 4) + 1] = Some(PieceKind::Bishop);
+        n /= 4;
+        back_rank[2 * (n % 4)] = Some(PieceKind::Bishop);
+        n /= 4;
+        place(&mut back_rank, n % 6, PieceKind::Queen);
+        n /= 6;
+        let (first_knight, second_knight) = KNIGHT_PLACEMENTS[n];
+        place(&mut back_rank, second_knight, PieceKind::Knight);
+        place(&mut back_rank, first_knight, PieceKind::Knight);
+        // The king always stands between the two rooks.
+        for kind in [PieceKind::Rook, PieceKind::King, PieceKind::Rook] {
+            place(&mut back_rank, 0, kind);
+        }
+
+        let mut board = ChessBoard::default();
+        for (file, kind) in File::ALL.into_iter().zip(back_rank) {
+            let kind = kind.expect("Every square of the back rank is filled");
+            for color in [Color::White, Color::Black] {
+                let square = Square::new(file, Rank::First.relative_to(color));
+                board.set_at(square, SquareContent::Occupied(Piece { kind, color }));
+            }
+        }
+        let mut rook_files = File::ALL
+            .into_iter()
+            .filter(|file| back_rank[file.index() as usize] == Some(PieceKind::Rook));
+        let queenside_rook = rook_files.next().expect("Two rooks on the back rank");
+        let kingside_rook = rook_files.next().expect("Two rooks on the back rank");
+        for color in [Color::White, Color::Black] {
+            board.set_castling_rook_file(color, CastlingSide::Queenside, queenside_rook);
+            board.set_castling_rook_file(color, CastlingSide::Kingside, kingside_rook);
+        }
+        Some(board)
+    }
+}
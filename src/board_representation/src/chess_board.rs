@@ -1,37 +1,67 @@
+use crate::bitboard::Bitboard;
 use crate::chess_piece::*;
+use crate::fen_error::FenError;
+use crate::square::{File, Rank, Square};
+use crate::zobrist::{ZOBRIST_KEYS, ZobristHash};
 use std::cmp::PartialEq;
 use std::fmt;
 use std::fmt::Formatter;
 use std::str::FromStr;
 
-#[derive(Copy, Clone, PartialEq)]
-pub enum Square {
+/// What stands on a square of the board.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SquareContent {
     Empty,
     Occupied(Piece),
 }
 
-impl fmt::Display for Square {
+impl fmt::Display for SquareContent {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Square::Empty => write!(f, "-"),
-            Square::Occupied(piece) => write!(f, "{}", piece),
+            SquareContent::Empty => write!(f, "-"),
+            SquareContent::Occupied(piece) => write!(f, "{}", piece),
         }
     }
 }
 
-type BitBoard = u64;
+/// The two ways to castle, named after the side of the board the king goes to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CastlingSide {
+    Kingside,
+    Queenside,
+}
+
+impl CastlingSide {
+    pub const ALL: [CastlingSide; 2] = [CastlingSide::Kingside, CastlingSide::Queenside];
+
+    /// The file the king lands on, whatever file it starts from.
+    pub fn king_destination(self) -> File {
+        match self {
+            CastlingSide::Kingside => File::G,
+            CastlingSide::Queenside => File::C,
+        }
+    }
 
-#[derive(Clone, Copy)]
+    /// The file the rook lands on, whatever file it starts from.
+    pub fn rook_destination(self) -> File {
+        match self {
+            CastlingSide::Kingside => File::F,
+            CastlingSide::Queenside => File::D,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct ChessBoard {
-    pub white_pieces: BitBoard,
-    pub black_pieces: BitBoard,
+    pub white_pieces: Bitboard,
+    pub black_pieces: Bitboard,
 
-    pub pawns: BitBoard,
-    pub knights: BitBoard,
-    pub bishops: BitBoard,
-    pub rooks: BitBoard,
-    pub queens: BitBoard,
-    pub kings: BitBoard,
+    pub pawns: Bitboard,
+    pub knights: Bitboard,
+    pub bishops: Bitboard,
+    pub rooks: Bitboard,
+    pub queens: Bitboard,
+    pub kings: Bitboard,
 
     /// bit 0: can_white_castle_kingside.
     /// bit 1: can_white_castle_queenside.
@@ -39,7 +69,18 @@ pub struct ChessBoard {
     /// bit 3: can_black_castle_queenside.
     /// bit 4: current_turn. (1 -> white, 0 -> black)
     /// bit 5-10: en_passant_target_square. (111111 -> None)
-    status: BitBoard,
+    status: u64,
+    /// Files of the rooks castling with the king, indexed like the castling bits of `status`.
+    /// The h- and a-files in standard chess, anywhere on the back rank in Chess960.
+    castling_rook_files: [File; 4],
+
+    /// Halfmoves since the last capture or pawn move, used for the fifty-move rule.
+    halfmove_clock: u16,
+    /// Starts at 1 and is incremented after each black move.
+    fullmove_number: u16,
+
+    /// Zobrist hash of the position, kept up to date by every setter.
+    hash: ZobristHash,
 }
 
 impl fmt::Display for ChessBoard {
@@ -48,11 +89,10 @@ impl fmt::Display for ChessBoard {
         // a8 ... h8
         // .........
         // a1 ... h1
-        for rank in (0..8).rev() {
-            write!(f, "{} ", rank + 1)?; // Rank label
-            for file in 0..8 {
-                let index = rank * 8 + file;
-                write!(f, " {}", self.at(index))?;
+        for rank in Rank::ALL.into_iter().rev() {
+            write!(f, "{} ", rank)?; // Rank label
+            for file in File::ALL {
+                write!(f, " {}", self.at(Square::new(file, rank)))?;
             }
             writeln!(f)?;
         }
@@ -63,63 +103,108 @@ impl fmt::Display for ChessBoard {
 
 impl Default for ChessBoard {
     fn default() -> Self {
-        Self {
-            white_pieces: 0x000000000000FFFF,
-            black_pieces: 0xFFFF000000000000,
-            pawns: 0x00FF00000000FF00,
-            knights: 0x4200000000000042,
-            bishops: 0x2400000000000024,
-            rooks: 0x8100000000000081,
-            queens: 0x0800000000000008,
-            kings: 0x1000000000000010,
+        let mut board = Self {
+            white_pieces: Bitboard(0x000000000000FFFF),
+            black_pieces: Bitboard(0xFFFF000000000000),
+            pawns: Bitboard(0x00FF00000000FF00),
+            knights: Bitboard(0x4200000000000042),
+            bishops: Bitboard(0x2400000000000024),
+            rooks: Bitboard(0x8100000000000081),
+            queens: Bitboard(0x0800000000000008),
+            kings: Bitboard(0x1000000000000010),
             status: 0x7FF,
-        }
+            castling_rook_files: DEFAULT_CASTLING_ROOK_FILES,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            hash: 0,
+        };
+        board.hash = board.compute_hash();
+        board
     }
 }
 
-pub type SquareIndex = u8;
-pub type UnsafeSquareIndex = i8;
+const WHITE_KINGSIDE_CASTLE_MASK: u64 = 1 << 0;
+const WHITE_QUEENSIDE_CASTLE_MASK: u64 = 1 << 1;
+const BLACK_KINGSIDE_CASTLE_MASK: u64 = 1 << 2;
+const BLACK_QUEENSIDE_CASTLE_MASK: u64 = 1 << 3;
+const CASTLING_RIGHTS_MASK: u64 = 15;
+const CURRENT_TURN_MASK: u64 = 1 << 4;
+const EN_PASSANT_MASK: u64 = 63 << 5;
+const DEFAULT_CASTLING_ROOK_FILES: [File; 4] = [File::H, File::A, File::H, File::A];
+
+fn castling_bit(color: Color, side: CastlingSide) -> usize {
+    match (color, side) {
+        (Color::White, CastlingSide::Kingside) => 0,
+        (Color::White, CastlingSide::Queenside) => 1,
+        (Color::Black, CastlingSide::Kingside) => 2,
+        (Color::Black, CastlingSide::Queenside) => 3,
+    }
+}
 
-const WHITE_KINGSIDE_CASTLE_MASK: BitBoard = 1 << 0;
-const WHITE_QUEENSIDE_CASTLE_MASK: BitBoard = 1 << 1;
-const BLACK_KINGSIDE_CASTLE_MASK: BitBoard = 1 << 2;
-const BLACK_QUEENSIDE_CASTLE_MASK: BitBoard = 1 << 3;
-const CURRENT_TURN_MASK: BitBoard = 1 << 4;
-const EN_PASSANT_MASK: BitBoard = 63 << 5;
+/// The file of the rook `side` of the king of `color` furthest from it, which is the castling
+/// rook of the `KQkq` letters of X-FEN. The files of a standard game when the king is not on
+/// the back rank or there is no such rook.
+fn outermost_rook_file(
+    rooks: Bitboard,
+    kings: Bitboard,
+    pieces: Bitboard,
+    color: Color,
+    side: CastlingSide,
+) -> File {
+    let back_rank = Rank::First.relative_to(color).bitboard();
+    let king_file = (kings & pieces & back_rank).first().map(|king| king.file());
+    let rook_files = (rooks & pieces & back_rank)
+        .into_iter()
+        .map(|rook| rook.file());
+    match (king_file, side) {
+        (Some(king_file), CastlingSide::Kingside) => {
+            rook_files.filter(|file| *file > king_file).max()
+        }
+        (Some(king_file), CastlingSide::Queenside) => {
+            rook_files.filter(|file| *file < king_file).min()
+        }
+        (None, _) => None,
+    }
+    .unwrap_or(DEFAULT_CASTLING_ROOK_FILES[castling_bit(color, side)])
+}
 
 impl ChessBoard {
-    pub fn at(&self, index: SquareIndex) -> Square {
-        let square_mask = 1 << index;
-
-        match square_mask & (self.white_pieces | self.black_pieces) {
-            0 => Square::Empty,
-            _ => {
-                let color = match square_mask & self.white_pieces {
-                    0 => Color::Black,
-                    _ => Color::White,
-                };
-                let kind = if square_mask & self.pawns != 0 {
-                    PieceKind::Pawn
-                } else if square_mask & self.knights != 0 {
-                    PieceKind::Knight
-                } else if square_mask & self.bishops != 0 {
-                    PieceKind::Bishop
-                } else if square_mask & self.rooks != 0 {
-                    PieceKind::Rook
-                } else if square_mask & self.queens != 0 {
-                    PieceKind::Queen
-                } else if square_mask & self.kings != 0 {
-                    PieceKind::King
-                } else {
-                    unreachable!("Mismatch between color bitboard and piece bitboard")
-                };
-                Square::Occupied(Piece { kind, color })
-            }
+    pub fn at(&self, square: Square) -> SquareContent {
+        if !self.occupancy().contains(square) {
+            return SquareContent::Empty;
         }
+        let color = if self.white_pieces.contains(square) {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let kind = if self.pawns.contains(square) {
+            PieceKind::Pawn
+        } else if self.knights.contains(square) {
+            PieceKind::Knight
+        } else if self.bishops.contains(square) {
+            PieceKind::Bishop
+        } else if self.rooks.contains(square) {
+            PieceKind::Rook
+        } else if self.queens.contains(square) {
+            PieceKind::Queen
+        } else if self.kings.contains(square) {
+            PieceKind::King
+        } else {
+            unreachable!("Mismatch between color bitboard and piece bitboard")
+        };
+        SquareContent::Occupied(Piece { kind, color })
     }
 
-    pub fn set_at(&mut self, index: SquareIndex, square: Square) {
-        let square_mask = 1 << index;
+    pub fn set_at(&mut self, square: Square, content: SquareContent) {
+        if let SquareContent::Occupied(piece) = self.at(square) {
+            self.hash ^= ZOBRIST_KEYS.piece(piece, square);
+        }
+        if let SquareContent::Occupied(piece) = content {
+            self.hash ^= ZOBRIST_KEYS.piece(piece, square);
+        }
+
+        let square_mask = square.bitboard();
         self.white_pieces &= !square_mask;
         self.black_pieces &= !square_mask;
         self.pawns &= !square_mask;
@@ -128,8 +213,8 @@ impl ChessBoard {
         self.rooks &= !square_mask;
         self.queens &= !square_mask;
         self.kings &= !square_mask;
-        match square {
-            Square::Occupied(piece) => {
+        match content {
+            SquareContent::Occupied(piece) => {
                 match piece.color {
                     Color::White => self.white_pieces |= square_mask,
                     Color::Black => self.black_pieces |= square_mask,
@@ -143,31 +228,67 @@ impl ChessBoard {
                     PieceKind::King => self.kings |= square_mask,
                 }
             }
-            Square::Empty => {}
+            SquareContent::Empty => {}
         }
     }
 
+    fn set_castling_right(&mut self, mask: u64, can_castle: bool) {
+        if can_castle != (self.status & mask != 0) {
+            self.status ^= mask;
+            self.hash ^= ZOBRIST_KEYS.castling_right(mask.trailing_zeros());
+        }
+    }
+
+    /// The four castling rights packed in the same order as the bits of `status`.
+    pub fn castling_rights(&self) -> u8 {
+        (self.status & CASTLING_RIGHTS_MASK) as u8
+    }
+
+    pub fn set_castling_rights(&mut self, castling_rights: u8) {
+        for castling_bit in 0..4 {
+            let mask = 1 << castling_bit;
+            self.set_castling_right(mask, castling_rights as u64 & mask != 0);
+        }
+    }
+
+    pub fn can_castle(&self, color: Color, side: CastlingSide) -> bool {
+        self.status & 1 << castling_bit(color, side) != 0
+    }
+
+    pub fn set_can_castle(&mut self, color: Color, side: CastlingSide, can_castle: bool) {
+        self.set_castling_right(1 << castling_bit(color, side), can_castle);
+    }
+
+    /// The file of the rook the king of `color` castles with on `side`, only meaningful while
+    /// the castling right is kept.
+    pub fn castling_rook_file(&self, color: Color, side: CastlingSide) -> File {
+        self.castling_rook_files[castling_bit(color, side)]
+    }
+
+    pub fn set_castling_rook_file(&mut self, color: Color, side: CastlingSide, file: File) {
+        self.castling_rook_files[castling_bit(color, side)] = file;
+    }
+
+    pub fn castling_rook_square(&self, color: Color, side: CastlingSide) -> Square {
+        Square::new(
+            self.castling_rook_file(color, side),
+            Rank::First.relative_to(color),
+        )
+    }
+
     pub fn can_white_castle_kingside(&self) -> bool {
         (self.status & WHITE_KINGSIDE_CASTLE_MASK) != 0
     }
 
     pub fn set_white_castle_kingside(&mut self, can_castle: bool) {
-        if can_castle {
-            self.status |= WHITE_KINGSIDE_CASTLE_MASK;
-        } else {
-            self.status &= !WHITE_KINGSIDE_CASTLE_MASK;
-        }
+        self.set_castling_right(WHITE_KINGSIDE_CASTLE_MASK, can_castle);
     }
     pub fn can_white_castle_queenside(&self) -> bool {
         (self.status & WHITE_QUEENSIDE_CASTLE_MASK) != 0
     }
 
     pub fn set_white_castle_queenside(&mut self, can_castle: bool) {
-        if can_castle {
-            self.status |= WHITE_QUEENSIDE_CASTLE_MASK;
-        } else {
-            self.status &= !WHITE_QUEENSIDE_CASTLE_MASK;
-        }
+        self.set_castling_right(WHITE_QUEENSIDE_CASTLE_MASK, can_castle);
     }
 
     pub fn can_black_castle_kingside(&self) -> bool {
@@ -175,11 +296,7 @@ impl ChessBoard {
     }
 
     pub fn set_black_castle_kingside(&mut self, can_castle: bool) {
-        if can_castle {
-            self.status |= BLACK_KINGSIDE_CASTLE_MASK;
-        } else {
-            self.status &= !BLACK_KINGSIDE_CASTLE_MASK;
-        }
+        self.set_castling_right(BLACK_KINGSIDE_CASTLE_MASK, can_castle);
     }
 
     pub fn can_black_castle_queenside(&self) -> bool {
@@ -187,11 +304,7 @@ impl ChessBoard {
     }
 
     pub fn set_black_castle_queenside(&mut self, can_castle: bool) {
-        if can_castle {
-            self.status |= BLACK_QUEENSIDE_CASTLE_MASK;
-        } else {
-            self.status &= !BLACK_QUEENSIDE_CASTLE_MASK;
-        }
+        self.set_castling_right(BLACK_QUEENSIDE_CASTLE_MASK, can_castle);
     }
 
     pub fn current_turn(&self) -> Color {
@@ -204,139 +317,372 @@ impl ChessBoard {
 
     pub fn next_turn(&mut self) {
         self.status ^= CURRENT_TURN_MASK;
+        self.hash ^= ZOBRIST_KEYS.black_to_move();
     }
 
-    pub fn en_passant_target_square(&self) -> Option<SquareIndex> {
+    pub fn en_passant_target_square(&self) -> Option<Square> {
         if self.status & EN_PASSANT_MASK == EN_PASSANT_MASK {
             None
         } else {
-            let square_index = (self.status & EN_PASSANT_MASK) >> 5;
-            debug_assert!(square_index < 64);
-            Some(square_index as SquareIndex)
+            Square::from_index(((self.status & EN_PASSANT_MASK) >> 5) as u8)
         }
     }
 
-    pub fn set_en_passant_target_square(&mut self, square: Option<SquareIndex>) {
+    pub fn set_en_passant_target_square(&mut self, square: Option<Square>) {
+        if let Some(square) = self.en_passant_target_square() {
+            self.hash ^= ZOBRIST_KEYS.en_passant(square);
+        }
+        if let Some(square) = square {
+            self.hash ^= ZOBRIST_KEYS.en_passant(square);
+        }
+
         self.status &= !EN_PASSANT_MASK;
         match square {
             None => {
                 self.status |= EN_PASSANT_MASK;
             }
-            Some(index) => {
-                self.status |= (index as BitBoard) << 5;
+            Some(square) => {
+                self.status |= (square.index() as u64) << 5;
             }
         }
         // TODO: reintroduce this assert
         // debug_assert_eq!(self.en_passant_target_square(), square);
     }
+
+    pub fn halfmove_clock(&self) -> u16 {
+        self.halfmove_clock
+    }
+
+    pub fn set_halfmove_clock(&mut self, halfmove_clock: u16) {
+        self.halfmove_clock = halfmove_clock;
+    }
+
+    pub fn fullmove_number(&self) -> u16 {
+        self.fullmove_number
+    }
+
+    pub fn set_fullmove_number(&mut self, fullmove_number: u16) {
+        self.fullmove_number = fullmove_number;
+    }
+
+    pub fn hash(&self) -> ZobristHash {
+        self.hash
+    }
+
+    /// Computes the Zobrist hash from scratch, `hash` returns the same value in constant time.
+    pub fn compute_hash(&self) -> ZobristHash {
+        let mut hash = 0;
+        for square in Square::ALL {
+            if let SquareContent::Occupied(piece) = self.at(square) {
+                hash ^= ZOBRIST_KEYS.piece(piece, square);
+            }
+        }
+        for castling_bit in 0..4 {
+            if self.status & (1 << castling_bit) != 0 {
+                hash ^= ZOBRIST_KEYS.castling_right(castling_bit);
+            }
+        }
+        if let Some(square) = self.en_passant_target_square() {
+            hash ^= ZOBRIST_KEYS.en_passant(square);
+        }
+        if self.current_turn() == Color::Black {
+            hash ^= ZOBRIST_KEYS.black_to_move();
+        }
+        hash
+    }
+
+    /// The FEN of the position. Castling rights are written in X-FEN: `KQkq` for the outermost
+    /// rooks, which covers every standard position, and the file of the rook otherwise.
+    pub fn to_fen(&self) -> String {
+        self.fen(false)
+    }
+
+    /// The FEN of the position with the castling rights written in Shredder-FEN, as the files
+    /// of the castling rooks: `HAha` for the standard starting position.
+    pub fn to_shredder_fen(&self) -> String {
+        self.fen(true)
+    }
+
+    fn fen(&self, shredder: bool) -> String {
+        let mut fen = String::new();
+
+        for rank in Rank::ALL.into_iter().rev() {
+            let mut empty_squares = 0;
+            for file in File::ALL {
+                match self.at(Square::new(file, rank)) {
+                    SquareContent::Empty => empty_squares += 1,
+                    SquareContent::Occupied(piece) => {
+                        if empty_squares > 0 {
+                            fen.push(char::from(b'0' + empty_squares));
+                            empty_squares = 0;
+                        }
+                        fen.push(piece.to_fen_char());
+                    }
+                }
+            }
+            if empty_squares > 0 {
+                fen.push(char::from(b'0' + empty_squares));
+            }
+            if rank != Rank::First {
+                fen.push('/');
+            }
+        }
+
+        fen.push(' ');
+        fen.push(match self.current_turn() {
+            Color::White => 'w',
+            Color::Black => 'b',
+        });
+
+        fen.push(' ');
+        let mut castling_rights = String::new();
+        for color in [Color::White, Color::Black] {
+            for side in CastlingSide::ALL {
+                if !self.can_castle(color, side) {
+                    continue;
+                }
+                let file = self.castling_rook_file(color, side);
+                let outermost =
+                    outermost_rook_file(self.rooks, self.kings, self.pieces_of(color), color, side);
+                let c = match side {
+                    _ if shredder || file != outermost => file.to_char().to_ascii_uppercase(),
+                    CastlingSide::Kingside => 'K',
+                    CastlingSide::Queenside => 'Q',
+                };
+                castling_rights.push(match color {
+                    Color::White => c,
+                    Color::Black => c.to_ascii_lowercase(),
+                });
+            }
+        }
+        if castling_rights.is_empty() {
+            fen.push('-');
+        } else {
+            fen.push_str(&castling_rights);
+        }
+
+        fen.push(' ');
+        match self.en_passant_target_square() {
+            None => fen.push('-'),
+            Some(square) => fen.push_str(&square.to_string()),
+        }
+
+        fen.push_str(&format!(
+            " {} {}",
+            self.halfmove_clock, self.fullmove_number
+        ));
+        fen
+    }
+}
+
+/// Splits `fen` on whitespace, pairing each field with the position of its first character.
+fn fen_fields(fen: &str) -> Vec<(usize, &str)> {
+    let mut fields = Vec::new();
+    let mut field_start = None;
+    for (position, (byte_index, c)) in fen.char_indices().enumerate() {
+        match (c.is_whitespace(), field_start) {
+            (true, Some((start_position, start_byte_index))) => {
+                fields.push((start_position, &fen[start_byte_index..byte_index]));
+                field_start = None;
+            }
+            (false, None) => field_start = Some((position, byte_index)),
+            _ => {}
+        }
+    }
+    if let Some((start_position, start_byte_index)) = field_start {
+        fields.push((start_position, &fen[start_byte_index..]));
+    }
+    fields
 }
 
 impl FromStr for ChessBoard {
-    type Err = String;
+    type Err = FenError;
 
     fn from_str(fen: &str) -> Result<Self, Self::Err> {
-        let mut white_pieces = 0;
-        let mut black_pieces = 0;
-        let mut pawns = 0;
-        let mut knights = 0;
-        let mut bishops = 0;
-        let mut rooks = 0;
-        let mut queens = 0;
-        let mut kings = 0;
+        let mut white_pieces = Bitboard::EMPTY;
+        let mut black_pieces = Bitboard::EMPTY;
+        let mut pawns = Bitboard::EMPTY;
+        let mut knights = Bitboard::EMPTY;
+        let mut bishops = Bitboard::EMPTY;
+        let mut rooks = Bitboard::EMPTY;
+        let mut queens = Bitboard::EMPTY;
+        let mut kings = Bitboard::EMPTY;
         let mut status = 0;
 
-        let parts = fen.split(" ").collect::<Vec<&str>>();
-        assert_eq!(parts.len(), 6, "Invalid FEN, expected 6 parts");
-
-        let rows = parts[0].split("/").collect::<Vec<&str>>();
-        assert_eq!(rows.len(), 8, "Invalid FEN, expected 8 rows");
+        let fields = fen_fields(fen);
+        if fields.len() < 4 || fields.len() > 6 {
+            return Err(FenError::WrongFieldCount {
+                found: fields.len(),
+            });
+        }
 
-        for (i, row) in rows.iter().enumerate() {
-            let mut j = 0;
-            for c in row.chars() {
-                match c {
-                    '1'..='8' => {
-                        j += c as usize - '0' as usize;
+        let (placement_position, placement) = fields[0];
+        let mut rank_index = 0;
+        let mut file = 0;
+        for (i, c) in placement.chars().enumerate() {
+            let position = placement_position + i;
+            let rank = 8 - rank_index as u8;
+            match c {
+                '/' => {
+                    if file < 8 {
+                        return Err(FenError::RankTooShort { rank, position });
+                    }
+                    rank_index += 1;
+                    file = 0;
+                    if rank_index == 8 {
+                        return Err(FenError::WrongRankCount {
+                            found: placement.split('/').count(),
+                            position,
+                        });
+                    }
+                }
+                '1'..='8' => {
+                    file += c as usize - '0' as usize;
+                    if file > 8 {
+                        return Err(FenError::RankTooLong { rank, position });
+                    }
+                }
+                _ => {
+                    let piece = Piece::from_fen_char(c)
+                        .ok_or(FenError::InvalidPiece { found: c, position })?;
+                    if file == 8 {
+                        return Err(FenError::RankTooLong { rank, position });
                     }
-                    _ => {
-                        let piece = match c {
-                            'P' => WHITE_PAWN,
-                            'N' => WHITE_KNIGHT,
-                            'B' => WHITE_BISHOP,
-                            'R' => WHITE_ROOK,
-                            'Q' => WHITE_QUEEN,
-                            'K' => WHITE_KING,
-                            'p' => BLACK_PAWN,
-                            'n' => BLACK_KNIGHT,
-                            'b' => BLACK_BISHOP,
-                            'r' => BLACK_ROOK,
-                            'q' => BLACK_QUEEN,
-                            'k' => BLACK_KING,
-                            _ => panic!(
-                                "Invalid FEN character, expected a piece representation (PNBRQKpnbrqk), found '{}'",
-                                c
-                            ),
-                        };
-
-                        let square_mask = 1 << ((7 - i) * 8 + j);
-                        match piece.color {
-                            Color::White => white_pieces |= square_mask,
-                            Color::Black => black_pieces |= square_mask,
-                        }
 
-                        match piece.kind {
-                            PieceKind::Pawn => pawns |= square_mask,
-                            PieceKind::Knight => knights |= square_mask,
-                            PieceKind::Bishop => bishops |= square_mask,
-                            PieceKind::Rook => rooks |= square_mask,
-                            PieceKind::Queen => queens |= square_mask,
-                            PieceKind::King => kings |= square_mask,
-                        }
+                    let square_mask =
+                        Square::new(File::ALL[file], Rank::ALL[7 - rank_index]).bitboard();
+                    match piece.color {
+                        Color::White => white_pieces |= square_mask,
+                        Color::Black => black_pieces |= square_mask,
+                    }
 
-                        j += 1;
+                    match piece.kind {
+                        PieceKind::Pawn => pawns |= square_mask,
+                        PieceKind::Knight => knights |= square_mask,
+                        PieceKind::Bishop => bishops |= square_mask,
+                        PieceKind::Rook => rooks |= square_mask,
+                        PieceKind::Queen => queens |= square_mask,
+                        PieceKind::King => kings |= square_mask,
                     }
+
+                    file += 1;
                 }
             }
         }
+        let placement_end = placement_position + placement.chars().count();
+        if rank_index < 7 {
+            return Err(FenError::WrongRankCount {
+                found: rank_index + 1,
+                position: placement_end,
+            });
+        }
+        if file < 8 {
+            return Err(FenError::RankTooShort {
+                rank: 1,
+                position: placement_end,
+            });
+        }
 
-        match parts[1] {
-            "w" => {
+        match fields[1] {
+            (_, "w") => {
                 status |= CURRENT_TURN_MASK;
             }
-            "b" => {}
-            _ => panic!(
-                "Invalid FEN, expected 'w' or 'b' for side to move, found {}",
-                parts[1]
-            ),
+            (_, "b") => {}
+            (position, side_to_move) => {
+                return Err(FenError::InvalidSideToMove {
+                    found: side_to_move.to_string(),
+                    position,
+                });
+            }
         };
 
-        if parts[2].contains("Q") {
-            status |= WHITE_QUEENSIDE_CASTLE_MASK;
-        }
-        if parts[2].contains("K") {
-            status |= WHITE_KINGSIDE_CASTLE_MASK;
-        }
-        if parts[2].contains("q") {
-            status |= BLACK_QUEENSIDE_CASTLE_MASK;
-        }
-        if parts[2].contains("k") {
-            status |= BLACK_KINGSIDE_CASTLE_MASK;
+        let (castling_position, castling) = fields[2];
+        let mut castling_rook_files = DEFAULT_CASTLING_ROOK_FILES;
+        if castling != "-" {
+            for (i, c) in castling.chars().enumerate() {
+                let error = FenError::InvalidCastlingRights {
+                    found: c,
+                    position: castling_position + i,
+                };
+                let color = if c.is_ascii_uppercase() {
+                    Color::White
+                } else {
+                    Color::Black
+                };
+                let pieces = match color {
+                    Color::White => white_pieces,
+                    Color::Black => black_pieces,
+                };
+                // Shredder-FEN and X-FEN name the rook by its file, KQkq is the outermost rook.
+                let lowercase = c.to_ascii_lowercase();
+                let (side, file) = match (lowercase, File::from_char(lowercase)) {
+                    ('k', _) => (CastlingSide::Kingside, None),
+                    ('q', _) => (CastlingSide::Queenside, None),
+                    (_, Some(file)) => {
+                        let back_rank = Rank::First.relative_to(color).bitboard();
+                        let king_file = (kings & pieces & back_rank)
+                            .first()
+                            .map_or(File::E, |king| king.file());
+                        if file == king_file {
+                            return Err(error);
+                        }
+                        let side = if file > king_file {
+                            CastlingSide::Kingside
+                        } else {
+                            CastlingSide::Queenside
+                        };
+                        (side, Some(file))
+                    }
+                    _ => return Err(error),
+                };
+                let mask = 1 << castling_bit(color, side);
+                if status & mask != 0 {
+                    return Err(error);
+                }
+                status |= mask;
+                castling_rook_files[castling_bit(color, side)] =
+                    file.unwrap_or_else(|| outermost_rook_file(rooks, kings, pieces, color, side));
+            }
         }
 
-        // TODO: this is not tested in perft
-        if parts[3] != "-" {
-            let col = (b'h' - parts[3].as_bytes()[0]) as usize;
-            let row = (parts[3].as_bytes()[1] - b'1') as usize;
-            if col > 7 || (row != 2 && row != 5) {
-                panic!(
-                    "Invalid FEN, en passant target square {} is not valid",
-                    parts[3]
-                )
+        match fields[3] {
+            (_, "-") => status |= EN_PASSANT_MASK,
+            (position, en_passant) => match en_passant.parse::<Square>() {
+                Ok(square) if matches!(square.rank(), Rank::Third | Rank::Sixth) => {
+                    status |= (square.index() as u64) << 5;
+                }
+                _ => {
+                    return Err(FenError::InvalidEnPassantTargetSquare {
+                        found: en_passant.to_string(),
+                        position,
+                    });
+                }
+            },
+        };
+
+        // The move counters are optional, many FEN strings in the wild omit them.
+        let halfmove_clock = match fields.get(4) {
+            None => 0,
+            Some((position, clock)) => {
+                clock.parse().map_err(|_| FenError::InvalidHalfmoveClock {
+                    found: clock.to_string(),
+                    position: *position,
+                })?
+            }
+        };
+        let fullmove_number = match fields.get(5) {
+            None => 1,
+            Some((position, number)) => {
+                number
+                    .parse()
+                    .map_err(|_| FenError::InvalidFullmoveNumber {
+                        found: number.to_string(),
+                        position: *position,
+                    })?
             }
-            status |= ((row * 8 + col) as BitBoard) << 5;
         };
 
-        Ok(Self {
+        let mut board = Self {
             white_pieces,
             black_pieces,
             pawns,
@@ -346,6 +692,12 @@ impl FromStr for ChessBoard {
             queens,
             kings,
             status,
-        })
+            castling_rook_files,
+            halfmove_clock,
+            fullmove_number,
+            hash: 0,
+        };
+        board.hash = board.compute_hash();
+        Ok(board)
     }
 }
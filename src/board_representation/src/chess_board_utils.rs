@@ -1,120 +1,54 @@
-use crate::chess_board::{ChessBoard, Square, SquareIndex, UnsafeSquareIndex};
+use crate::bitboard::Bitboard;
+use crate::chess_board::{ChessBoard, SquareContent};
 use crate::chess_piece::{Color, Piece};
-
-pub fn within_bounds(index: UnsafeSquareIndex) -> Option<SquareIndex> {
-    if index >= 0 && index < 64 {
-        Some(index as SquareIndex)
-    } else {
-        None
-    }
-}
+use crate::square::Square;
 
 impl ChessBoard {
     pub fn for_each_piece<F>(&self, mut block: F)
     where
-        F: FnMut(SquareIndex, &Piece),
+        F: FnMut(Square, &Piece),
     {
-        for index in 0..64 {
-            match self.at(index) {
-                Square::Empty => {}
-                Square::Occupied(piece) => {
-                    block(index, &piece);
-                }
-            }
-        }
-    }
-
-    pub fn within_bounds_and_empty(&self, index: UnsafeSquareIndex) -> Option<SquareIndex> {
-        match within_bounds(index) {
-            None => None,
-            Some(index) => match self.at(index) {
-                Square::Empty => Some(index),
-                Square::Occupied(_) => None,
-            },
-        }
-    }
-
-    fn occupied_by_opponent(&self, index: SquareIndex, color: &Color) -> Option<SquareIndex> {
-        match self.at(index) {
-            Square::Empty => None,
-            Square::Occupied(piece) => {
-                if piece.color != *color {
-                    Some(index)
-                } else {
-                    None
-                }
+        for square in self.occupancy() {
+            if let SquareContent::Occupied(piece) = self.at(square) {
+                block(square, &piece);
             }
         }
     }
 
-    pub fn within_bounds_and_occupied_by_opponent(
-        &self,
-        index: UnsafeSquareIndex,
-        color: &Color,
-    ) -> Option<SquareIndex> {
-        match within_bounds(index) {
-            None => None,
-            Some(index) => self.occupied_by_opponent(index, color),
+    pub fn maybe_piece_at(&self, square: Square) -> Option<Piece> {
+        match self.at(square) {
+            SquareContent::Empty => None,
+            SquareContent::Occupied(piece) => Some(piece),
         }
     }
 
-    pub fn within_bounds_and_pawn_take_target(
-        &self,
-        index: UnsafeSquareIndex,
-        color: &Color,
-    ) -> Option<SquareIndex> {
-        match within_bounds(index) {
-            None => None,
-            Some(index) => {
-                if self.en_passant_target_square() == Some(index) {
-                    Some(index)
-                } else {
-                    self.occupied_by_opponent(index, color)
-                }
-            }
+    pub fn piece_at_source_or_panic(self, square: Square) -> Piece {
+        match self.at(square) {
+            SquareContent::Occupied(piece) => piece,
+            SquareContent::Empty => panic!("Invalid move: Cannot move from empty square"),
         }
     }
 
-    pub fn maybe_piece_at(&self, index: UnsafeSquareIndex) -> Option<Piece> {
-        match within_bounds(index) {
-            None => None,
-            Some(index) => match self.at(index) {
-                Square::Empty => None,
-                Square::Occupied(piece) => Some(piece),
-            },
-        }
+    pub fn contains_piece_at(self, square: Square, piece_to_find: Piece) -> bool {
+        self.maybe_piece_at(square) == Some(piece_to_find)
     }
 
-    pub fn piece_at_source_or_panic(self, index: SquareIndex) -> Piece {
-        match self.at(index) {
-            Square::Occupied(piece) => piece,
-            Square::Empty => panic!("Invalid move: Cannot move from empty square"),
-        }
+    /// The square of the king of `color`. Panics if it has none, `validate` rejects such
+    /// positions.
+    pub fn find_king(&self, color: Color) -> Square {
+        (self.pieces_of(color) & self.kings)
+            .first()
+            .unwrap_or_else(|| panic!("No {:?} king on the board", color))
     }
 
-    pub fn contains_piece_at(self, index: UnsafeSquareIndex, piece_to_find: Piece) -> bool {
-        match self.maybe_piece_at(index) {
-            None => false,
-            Some(piece) => piece == piece_to_find,
+    pub fn pieces_of(&self, color: Color) -> Bitboard {
+        match color {
+            Color::White => self.white_pieces,
+            Color::Black => self.black_pieces,
         }
     }
 
-    pub fn find_king(&self, color: Color) -> SquareIndex {
-        let king_bit_board = match color {
-            Color::White => self.white_pieces & self.kings,
-            Color::Black => self.black_pieces & self.kings,
-        };
-        king_bit_board.trailing_zeros() as SquareIndex
+    pub fn occupancy(&self) -> Bitboard {
+        self.white_pieces | self.black_pieces
     }
 }
-
-pub const FILE_MASK: [u64; 8] = [
-    0x0101010101010101, // FILE A
-    0x0202020202020202, // FILE B
-    0x0404040404040404, // FILE C
-    0x0808080808080808, // FILE D
-    0x1010101010101010, // FILE E
-    0x2020202020202020, // FILE F
-    0x4040404040404040, // FILE G
-    0x8080808080808080, // FILE H
-];
@@ -2,7 +2,7 @@ use std::fmt;
 use std::fmt::Formatter;
 use std::ops::Not;
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum PieceKind {
     Pawn,
     Rook,
@@ -19,7 +19,7 @@ pub const PROMOTABLE_KINDS: [PieceKind; 4] = [
     PieceKind::Knight,
 ];
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Color {
     White,
     Black,
@@ -36,7 +36,7 @@ impl Not for Color {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct Piece {
     pub kind: PieceKind,
     pub color: Color,
@@ -102,6 +102,41 @@ pub const BLACK_KING: Piece = Piece {
     color: Color::Black,
 };
 
+impl Piece {
+    pub fn from_fen_char(c: char) -> Option<Piece> {
+        match c {
+            'P' => Some(WHITE_PAWN),
+            'N' => Some(WHITE_KNIGHT),
+            'B' => Some(WHITE_BISHOP),
+            'R' => Some(WHITE_ROOK),
+            'Q' => Some(WHITE_QUEEN),
+            'K' => Some(WHITE_KING),
+            'p' => Some(BLACK_PAWN),
+            'n' => Some(BLACK_KNIGHT),
+            'b' => Some(BLACK_BISHOP),
+            'r' => Some(BLACK_ROOK),
+            'q' => Some(BLACK_QUEEN),
+            'k' => Some(BLACK_KING),
+            _ => None,
+        }
+    }
+
+    pub fn to_fen_char(self) -> char {
+        let c = match self.kind {
+            PieceKind::Pawn => 'p',
+            PieceKind::Rook => 'r',
+            PieceKind::Knight => 'n',
+            PieceKind::Bishop => 'b',
+            PieceKind::Queen => 'q',
+            PieceKind::King => 'k',
+        };
+        match self.color {
+            Color::White => c.to_ascii_uppercase(),
+            Color::Black => c,
+        }
+    }
+}
+
 impl fmt::Display for Piece {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let symbol = match (self.color, self.kind) {
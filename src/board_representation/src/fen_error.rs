@@ -0,0 +1,162 @@
+use crate::position_error::PositionError;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FenField {
+    PiecePlacement,
+    SideToMove,
+    CastlingRights,
+    EnPassantTargetSquare,
+    HalfmoveClock,
+    FullmoveNumber,
+}
+
+impl fmt::Display for FenField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FenField::PiecePlacement => "piece placement",
+            FenField::SideToMove => "side to move",
+            FenField::CastlingRights => "castling rights",
+            FenField::EnPassantTargetSquare => "en passant target square",
+            FenField::HalfmoveClock => "halfmove clock",
+            FenField::FullmoveNumber => "fullmove number",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Reason why a FEN string could not be parsed.
+///
+/// `position` is always the index (in characters) of the offending character in the FEN string.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FenError {
+    /// A FEN needs the first 4 fields, the move counters are optional.
+    WrongFieldCount {
+        found: usize,
+    },
+    WrongRankCount {
+        found: usize,
+        position: usize,
+    },
+    InvalidPiece {
+        found: char,
+        position: usize,
+    },
+    /// `rank` is numbered as on the board, from 8 (the first rank in the FEN) to 1.
+    RankTooLong {
+        rank: u8,
+        position: usize,
+    },
+    RankTooShort {
+        rank: u8,
+        position: usize,
+    },
+    InvalidSideToMove {
+        found: String,
+        position: usize,
+    },
+    InvalidCastlingRights {
+        found: char,
+        position: usize,
+    },
+    InvalidEnPassantTargetSquare {
+        found: String,
+        position: usize,
+    },
+    InvalidHalfmoveClock {
+        found: String,
+        position: usize,
+    },
+    InvalidFullmoveNumber {
+        found: String,
+        position: usize,
+    },
+    /// The FEN is well formed but the position breaks the rules of chess.
+    /// Only returned by `ChessBoard::from_fen_validated` and `from_chess960_fen_validated`.
+    InvalidPosition {
+        error: PositionError,
+    },
+}
+
+impl FenError {
+    /// The field containing the error, `None` when the fields themselves could not be told apart.
+    pub fn field(&self) -> Option<FenField> {
+        match self {
+            FenError::WrongFieldCount { .. } | FenError::InvalidPosition { .. } => None,
+            FenError::WrongRankCount { .. }
+            | FenError::InvalidPiece { .. }
+            | FenError::RankTooLong { .. }
+            | FenError::RankTooShort { .. } => Some(FenField::PiecePlacement),
+            FenError::InvalidSideToMove { .. } => Some(FenField::SideToMove),
+            FenError::InvalidCastlingRights { .. } => Some(FenField::CastlingRights),
+            FenError::InvalidEnPassantTargetSquare { .. } => Some(FenField::EnPassantTargetSquare),
+            FenError::InvalidHalfmoveClock { .. } => Some(FenField::HalfmoveClock),
+            FenError::InvalidFullmoveNumber { .. } => Some(FenField::FullmoveNumber),
+        }
+    }
+
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            FenError::WrongFieldCount { .. } | FenError::InvalidPosition { .. } => None,
+            FenError::WrongRankCount { position, .. }
+            | FenError::InvalidPiece { position, .. }
+            | FenError::RankTooLong { position, .. }
+            | FenError::RankTooShort { position, .. }
+            | FenError::InvalidSideToMove { position, .. }
+            | FenError::InvalidCastlingRights { position, .. }
+            | FenError::InvalidEnPassantTargetSquare { position, .. }
+            | FenError::InvalidHalfmoveClock { position, .. }
+            | FenError::InvalidFullmoveNumber { position, .. } => Some(*position),
+        }
+    }
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid FEN")?;
+        if let (Some(field), Some(position)) = (self.field(), self.position()) {
+            write!(f, " {} at position {}", field, position)?;
+        }
+        match self {
+            FenError::WrongFieldCount { found } => {
+                write!(f, ", expected 4 to 6 fields, found {}", found)
+            }
+            FenError::WrongRankCount { found, .. } => {
+                write!(f, ", expected 8 ranks, found {}", found)
+            }
+            FenError::InvalidPiece { found, .. } => write!(
+                f,
+                ", expected a piece representation (PNBRQKpnbrqk) or a digit from 1 to 8, found '{}'",
+                found
+            ),
+            FenError::RankTooLong { rank, .. } => {
+                write!(f, ", rank {} has more than 8 files", rank)
+            }
+            FenError::RankTooShort { rank, .. } => {
+                write!(f, ", rank {} has less than 8 files", rank)
+            }
+            FenError::InvalidSideToMove { found, .. } => {
+                write!(f, ", expected 'w' or 'b', found '{}'", found)
+            }
+            FenError::InvalidCastlingRights { found, .. } => write!(
+                f,
+                ", expected '-' or each of 'KQkq' at most once, found '{}'",
+                found
+            ),
+            FenError::InvalidEnPassantTargetSquare { found, .. } => write!(
+                f,
+                ", expected '-' or a square on the third or sixth rank, found '{}'",
+                found
+            ),
+            FenError::InvalidHalfmoveClock { found, .. }
+            | FenError::InvalidFullmoveNumber { found, .. } => {
+                write!(f, ", expected a number, found '{}'", found)
+            }
+            FenError::InvalidPosition { error } => write!(f, ", {}", error),
+        }
+    }
+}
+
+impl Error for FenError {}
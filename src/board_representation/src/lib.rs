@@ -1,3 +1,11 @@
+pub mod attacks;
+pub mod bitboard;
+mod chess960;
 pub mod chess_board;
 pub mod chess_board_utils;
 pub mod chess_piece;
+pub mod fen_error;
+pub mod position_error;
+pub mod square;
+mod validation;
+pub mod zobrist;
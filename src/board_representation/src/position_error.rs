@@ -0,0 +1,78 @@
+use crate::chess_piece::Color;
+use crate::square::Square;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// A rule of chess broken by a position, found by `ChessBoard::validate`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PositionViolation {
+    MissingKing {
+        color: Color,
+    },
+    TooManyKings {
+        color: Color,
+        found: u32,
+    },
+    PawnOnBackRank {
+        square: Square,
+    },
+    /// The king or the castling rook is not on its starting square: the e-file and the a- or
+    /// h-file, or in Chess960 anywhere on the back rank with the rook on that side of the king.
+    /// `right` is one of `KQkq`, as in FEN.
+    InvalidCastlingRight {
+        right: char,
+    },
+    /// The en passant target square is not behind a pawn that just moved two squares.
+    InvalidEnPassantTargetSquare {
+        square: Square,
+    },
+    /// The side that just moved left its king in check.
+    OpponentInCheck,
+}
+
+impl fmt::Display for PositionViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionViolation::MissingKing { color } => write!(f, "{:?} has no king", color),
+            PositionViolation::TooManyKings { color, found } => {
+                write!(f, "{:?} has {} kings", color, found)
+            }
+            PositionViolation::PawnOnBackRank { square } => {
+                write!(f, "pawn on {}, on the first or last rank", square)
+            }
+            PositionViolation::InvalidCastlingRight { right } => write!(
+                f,
+                "castling right '{}' without the king and rook on their starting squares",
+                right
+            ),
+            PositionViolation::InvalidEnPassantTargetSquare { square } => write!(
+                f,
+                "en passant target square {} is not behind a pawn that just moved two squares",
+                square
+            ),
+            PositionViolation::OpponentInCheck => {
+                write!(f, "the side not to move is in check")
+            }
+        }
+    }
+}
+
+/// Every rule of chess broken by a position, in the order they were checked.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PositionError {
+    pub violations: Vec<PositionViolation>,
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid position")?;
+        for (i, violation) in self.violations.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { ", " };
+            write!(f, "{}{}", separator, violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for PositionError {}
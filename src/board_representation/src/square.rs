@@ -0,0 +1,325 @@
+use crate::bitboard::Bitboard;
+use crate::chess_piece::Color;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum File {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+}
+
+impl File {
+    pub const ALL: [File; 8] = [
+        File::A,
+        File::B,
+        File::C,
+        File::D,
+        File::E,
+        File::F,
+        File::G,
+        File::H,
+    ];
+
+    pub fn from_index(index: u8) -> Option<File> {
+        File::ALL.get(index as usize).copied()
+    }
+
+    /// 0 for the a-file, 7 for the h-file.
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// The file `delta` files to the right, `None` past the edge of the board.
+    pub fn offset(self, delta: i8) -> Option<File> {
+        File::from_index((self.index() as i8).checked_add(delta)? as u8)
+    }
+
+    pub fn from_char(c: char) -> Option<File> {
+        match c {
+            'a'..='h' => File::from_index(c as u8 - b'a'),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        char::from(b'a' + self.index())
+    }
+
+    pub const fn bitboard(self) -> Bitboard {
+        Bitboard(0x0101_0101_0101_0101 << self.index())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Rank {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+    Eighth,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 8] = [
+        Rank::First,
+        Rank::Second,
+        Rank::Third,
+        Rank::Fourth,
+        Rank::Fifth,
+        Rank::Sixth,
+        Rank::Seventh,
+        Rank::Eighth,
+    ];
+
+    pub fn from_index(index: u8) -> Option<Rank> {
+        Rank::ALL.get(index as usize).copied()
+    }
+
+    /// 0 for the first rank, 7 for the eighth rank.
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// The rank `delta` ranks up, towards the black side, `None` past the edge of the board.
+    pub fn offset(self, delta: i8) -> Option<Rank> {
+        Rank::from_index((self.index() as i8).checked_add(delta)? as u8)
+    }
+
+    /// This rank seen from the side of `color`: `Rank::Second.relative_to(Color::Black)` is
+    /// the seventh rank, where the black pawns start.
+    pub fn relative_to(self, color: Color) -> Rank {
+        match color {
+            Color::White => self,
+            Color::Black => Rank::ALL[7 - self.index() as usize],
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Rank> {
+        match c {
+            '1'..='8' => Rank::from_index(c as u8 - b'1'),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        char::from(b'1' + self.index())
+    }
+
+    pub const fn bitboard(self) -> Bitboard {
+        Bitboard(0xFF << (8 * self.index()))
+    }
+}
+
+/// A square of the board, indexed from 0 for a1 to 63 for h8, rank by rank.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Square(u8);
+
+macro_rules! square_constants {
+    ($($name:ident = $index:expr),* $(,)?) => {
+        $(pub const $name: Square = Square($index);)*
+    };
+}
+
+impl Square {
+    #[rustfmt::skip]
+    square_constants! {
+        A1 = 0, B1 = 1, C1 = 2, D1 = 3, E1 = 4, F1 = 5, G1 = 6, H1 = 7,
+        A2 = 8, B2 = 9, C2 = 10, D2 = 11, E2 = 12, F2 = 13, G2 = 14, H2 = 15,
+        A3 = 16, B3 = 17, C3 = 18, D3 = 19, E3 = 20, F3 = 21, G3 = 22, H3 = 23,
+        A4 = 24, B4 = 25, C4 = 26, D4 = 27, E4 = 28, F4 = 29, G4 = 30, H4 = 31,
+        A5 = 32, B5 = 33, C5 = 34, D5 = 35, E5 = 36, F5 = 37, G5 = 38, H5 = 39,
+        A6 = 40, B6 = 41, C6 = 42, D6 = 43, E6 = 44, F6 = 45, G6 = 46, H6 = 47,
+        A7 = 48, B7 = 49, C7 = 50, D7 = 51, E7 = 52, F7 = 53, G7 = 54, H7 = 55,
+        A8 = 56, B8 = 57, C8 = 58, D8 = 59, E8 = 60, F8 = 61, G8 = 62, H8 = 63,
+    }
+
+    /// Every square, from a1 to h8. Indexing it with `index & 63` needs no bounds check.
+    pub const ALL: [Square; 64] = {
+        let mut all = [Square(0); 64];
+        let mut index = 0;
+        while index < 64 {
+            all[index] = Square(index as u8);
+            index += 1;
+        }
+        all
+    };
+
+    pub const fn new(file: File, rank: Rank) -> Square {
+        Square(rank.index() * 8 + file.index())
+    }
+
+    pub fn from_index(index: u8) -> Option<Square> {
+        Square::ALL.get(index as usize).copied()
+    }
+
+    /// For indexes known to be on the board, such as the trailing zeros of a non-empty bitboard.
+    pub(crate) const fn from_index_unchecked(index: u8) -> Square {
+        debug_assert!(index < 64);
+        Square(index)
+    }
+
+    pub const fn index(self) -> u8 {
+        self.0
+    }
+
+    pub fn file(self) -> File {
+        File::ALL[(self.0 % 8) as usize]
+    }
+
+    pub fn rank(self) -> Rank {
+        Rank::ALL[(self.0 / 8) as usize]
+    }
+
+    /// The square `file_delta` files to the right and `rank_delta` ranks up, `None` past the
+    /// edge of the board.
+    pub fn offset(self, file_delta: i8, rank_delta: i8) -> Option<Square> {
+        Some(Square::new(
+            self.file().offset(file_delta)?,
+            self.rank().offset(rank_delta)?,
+        ))
+    }
+
+    /// The square in front of this one, from the side of `color`.
+    pub fn forward(self, color: Color) -> Option<Square> {
+        match color {
+            Color::White => self.offset(0, 1),
+            Color::Black => self.offset(0, -1),
+        }
+    }
+
+    /// The square behind this one, from the side of `color`.
+    pub fn backward(self, color: Color) -> Option<Square> {
+        self.forward(!color)
+    }
+
+    pub const fn bitboard(self) -> Bitboard {
+        Bitboard(1 << self.0)
+    }
+
+    /// The diagonal through this square going up to the right, like a1-h8.
+    pub fn diagonal(self) -> Bitboard {
+        const A1_H8: u64 = 0x8040_2010_0804_0201;
+        let shift = 8 * (self.file().index() as i8 - self.rank().index() as i8);
+        if shift >= 0 {
+            Bitboard(A1_H8 >> shift)
+        } else {
+            Bitboard(A1_H8 << -shift)
+        }
+    }
+
+    /// The diagonal through this square going up to the left, like h1-a8.
+    pub fn anti_diagonal(self) -> Bitboard {
+        const H1_A8: u64 = 0x0102_0408_1020_4080;
+        let shift = 8 * (self.file().index() as i8 + self.rank().index() as i8 - 7);
+        if shift >= 0 {
+            Bitboard(H1_A8 << shift)
+        } else {
+            Bitboard(H1_A8 >> -shift)
+        }
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.file().to_char(), self.rank().to_char())
+    }
+}
+
+impl fmt::Debug for Square {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            self.file().to_char().to_ascii_uppercase(),
+            self.rank().to_char()
+        )
+    }
+}
+
+impl fmt::Display for File {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SquareParseError {
+    pub found: String,
+}
+
+impl fmt::Display for SquareParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid square, expected a file from a to h and a rank from 1 to 8, found '{}'",
+            self.found
+        )
+    }
+}
+
+impl Error for SquareParseError {}
+
+impl FromStr for Square {
+    type Err = SquareParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let file_and_rank = match (chars.next(), chars.next(), chars.next()) {
+            (Some(file), Some(rank), None) => File::from_char(file).zip(Rank::from_char(rank)),
+            _ => None,
+        };
+        file_and_rank
+            .map(|(file, rank)| Square::new(file, rank))
+            .ok_or_else(|| SquareParseError {
+                found: s.to_string(),
+            })
+    }
+}
+
+impl FromStr for File {
+    type Err = SquareParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => File::from_char(c),
+            _ => None,
+        }
+        .ok_or_else(|| SquareParseError {
+            found: s.to_string(),
+        })
+    }
+}
+
+impl FromStr for Rank {
+    type Err = SquareParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Rank::from_char(c),
+            _ => None,
+        }
+        .ok_or_else(|| SquareParseError {
+            found: s.to_string(),
+        })
+    }
+}
@@ -0,0 +1,145 @@
+use crate::attacks::{bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks};
+use crate::bitboard::Bitboard;
+use crate::chess_board::{CastlingSide, ChessBoard};
+use crate::chess_piece::Color;
+use crate::fen_error::FenError;
+use crate::position_error::{PositionError, PositionViolation};
+use crate::square::{File, Rank, Square};
+use std::str::FromStr;
+
+impl ChessBoard {
+    /// Checks that the position can occur in a game, listing every rule it breaks.
+    ///
+    /// Parsing a FEN and `set_at` accept any position, but move generation and search
+    /// expect exactly one king per side and consistent castling and en passant state.
+    /// Castling rights need the king on the e-file and the rook on the a- or h-file.
+    pub fn validate(&self) -> Result<(), PositionError> {
+        self.validate_castling_as(false)
+    }
+
+    /// Validates like `validate`, but with the castling rights of Chess960: the king may be
+    /// anywhere on the back rank with its castling rook on that side of it.
+    pub fn validate_chess960(&self) -> Result<(), PositionError> {
+        self.validate_castling_as(true)
+    }
+
+    fn validate_castling_as(&self, chess960: bool) -> Result<(), PositionError> {
+        let mut violations = Vec::new();
+
+        for color in [Color::White, Color::Black] {
+            match (self.pieces_of(color) & self.kings).count() {
+                0 => violations.push(PositionViolation::MissingKing { color }),
+                1 => {}
+                found => violations.push(PositionViolation::TooManyKings { color, found }),
+            }
+        }
+
+        for square in self.pawns & (Rank::First.bitboard() | Rank::Eighth.bitboard()) {
+            violations.push(PositionViolation::PawnOnBackRank { square });
+        }
+
+        for color in [Color::White, Color::Black] {
+            let pieces = self.pieces_of(color);
+            let king = (pieces & self.kings & Rank::First.relative_to(color).bitboard()).first();
+            for side in CastlingSide::ALL {
+                if !self.can_castle(color, side) {
+                    continue;
+                }
+                let rook = self.castling_rook_square(color, side);
+                let on_starting_squares = king.is_some_and(|king| match (chess960, side) {
+                    (true, CastlingSide::Kingside) => rook.file() > king.file(),
+                    (true, CastlingSide::Queenside) => rook.file() < king.file(),
+                    (false, CastlingSide::Kingside) => {
+                        king.file() == File::E && rook.file() == File::H
+                    }
+                    (false, CastlingSide::Queenside) => {
+                        king.file() == File::E && rook.file() == File::A
+                    }
+                });
+                if !(on_starting_squares && (pieces & self.rooks).contains(rook)) {
+                    let right = match side {
+                        CastlingSide::Kingside => 'K',
+                        CastlingSide::Queenside => 'Q',
+                    };
+                    violations.push(PositionViolation::InvalidCastlingRight {
+                        right: match color {
+                            Color::White => right,
+                            Color::Black => right.to_ascii_lowercase(),
+                        },
+                    });
+                }
+            }
+        }
+
+        if let Some(square) = self.en_passant_target_square()
+            && !self.is_en_passant_target_square_valid(square)
+        {
+            violations.push(PositionViolation::InvalidEnPassantTargetSquare { square });
+        }
+
+        let opponent = !self.current_turn();
+        let opponent_king = self.pieces_of(opponent) & self.kings;
+        if let Some(king) = opponent_king.first()
+            && opponent_king.count() == 1
+            && !self.attackers(king, self.current_turn()).is_empty()
+        {
+            violations.push(PositionViolation::OpponentInCheck);
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(PositionError { violations })
+        }
+    }
+
+    /// The pawn that just moved two squares stands in front of the target square, and both the
+    /// target square and the square it came from are empty.
+    fn is_en_passant_target_square_valid(&self, square: Square) -> bool {
+        let pawn_color = !self.current_turn();
+        if square.rank() != Rank::Third.relative_to(pawn_color) {
+            return false;
+        }
+        let (Some(pawn_square), Some(origin_square)) =
+            (square.forward(pawn_color), square.backward(pawn_color))
+        else {
+            return false;
+        };
+        (self.pieces_of(pawn_color) & self.pawns).contains(pawn_square)
+            && (self.occupancy() & (square.bitboard() | origin_square.bitboard())).is_empty()
+    }
+
+    fn attackers(&self, square: Square, color: Color) -> Bitboard {
+        let occupancy = self.occupancy();
+        let attackers = knight_attacks(square) & self.knights
+            | king_attacks(square) & self.kings
+            | pawn_attacks(square, !color) & self.pawns
+            | rook_attacks(square, occupancy) & (self.rooks | self.queens)
+            | bishop_attacks(square, occupancy) & (self.bishops | self.queens);
+        attackers & self.pieces_of(color)
+    }
+
+    /// Parses `fen` like `from_str`, then rejects positions that break the rules of chess.
+    /// Castling rights naming the rooks by their files, as in Shredder-FEN and X-FEN, are
+    /// validated as in Chess960, `KQkq` as in standard chess.
+    pub fn from_fen_validated(fen: &str) -> Result<Self, FenError> {
+        let board = ChessBoard::from_str(fen)?;
+        let castling = fen.split_whitespace().nth(2).unwrap_or("-");
+        let validation = if castling.chars().all(|c| "KQkq-".contains(c)) {
+            board.validate()
+        } else {
+            board.validate_chess960()
+        };
+        validation.map_err(|error| FenError::InvalidPosition { error })?;
+        Ok(board)
+    }
+
+    /// Parses `fen` like `from_str`, then rejects positions that break the rules of Chess960.
+    pub fn from_chess960_fen_validated(fen: &str) -> Result<Self, FenError> {
+        let board = ChessBoard::from_str(fen)?;
+        board
+            .validate_chess960()
+            .map_err(|error| FenError::InvalidPosition { error })?;
+        Ok(board)
+    }
+}
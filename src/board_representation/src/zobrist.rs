@@ -0,0 +1,98 @@
+use crate::chess_piece::{Color, Piece, PieceKind};
+use crate::square::Square;
+
+pub type ZobristHash = u64;
+
+/// Random keys xor-ed together to build the hash of a position.
+/// Generated at compile time with a fixed seed, so hashes are stable across runs.
+pub(crate) struct ZobristKeys {
+    /// Indexed by `piece_index(piece)` and square.
+    pieces: [[ZobristHash; 64]; 12],
+    /// One key per castling right, indexed like the castling bits of the board status.
+    castling_rights: [ZobristHash; 4],
+    en_passant_files: [ZobristHash; 8],
+    /// Xor-ed in when black is to move.
+    black_to_move: ZobristHash,
+}
+
+const fn xorshift(state: u64) -> u64 {
+    let mut x = state;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    x
+}
+
+const fn generate_keys() -> ZobristKeys {
+    let mut state = 0x5CA3_0124_C4E5_5E00;
+    let mut pieces = [[0; 64]; 12];
+    let mut piece = 0;
+    while piece < 12 {
+        let mut square = 0;
+        while square < 64 {
+            state = xorshift(state);
+            pieces[piece][square] = state;
+            square += 1;
+        }
+        piece += 1;
+    }
+
+    let mut castling_rights = [0; 4];
+    let mut i = 0;
+    while i < 4 {
+        state = xorshift(state);
+        castling_rights[i] = state;
+        i += 1;
+    }
+
+    let mut en_passant_files = [0; 8];
+    let mut i = 0;
+    while i < 8 {
+        state = xorshift(state);
+        en_passant_files[i] = state;
+        i += 1;
+    }
+
+    ZobristKeys {
+        pieces,
+        castling_rights,
+        en_passant_files,
+        black_to_move: xorshift(state),
+    }
+}
+
+pub(crate) const ZOBRIST_KEYS: ZobristKeys = generate_keys();
+
+fn piece_index(piece: Piece) -> usize {
+    let kind_index = match piece.kind {
+        PieceKind::Pawn => 0,
+        PieceKind::Knight => 1,
+        PieceKind::Bishop => 2,
+        PieceKind::Rook => 3,
+        PieceKind::Queen => 4,
+        PieceKind::King => 5,
+    };
+    match piece.color {
+        Color::White => kind_index,
+        Color::Black => kind_index + 6,
+    }
+}
+
+impl ZobristKeys {
+    pub(crate) fn piece(&self, piece: Piece, square: Square) -> ZobristHash {
+        self.pieces[piece_index(piece)][square.index() as usize]
+    }
+
+    /// `castling_bit` is the position of the right in the board status (0 to 3).
+    pub(crate) fn castling_right(&self, castling_bit: u32) -> ZobristHash {
+        self.castling_rights[castling_bit as usize]
+    }
+
+    pub(crate) fn en_passant(&self, square: Square) -> ZobristHash {
+        self.en_passant_files[square.file().index() as usize]
+    }
+
+    pub(crate) fn black_to_move(&self) -> ZobristHash {
+        self.black_to_move
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod attacks {
+    use board_representation::attacks::{
+        between, bishop_attacks, king_attacks, knight_attacks, line, pawn_attacks, queen_attacks,
+        rook_attacks,
+    };
+    use board_representation::bitboard::Bitboard;
+    use board_representation::chess_piece::Color;
+    use board_representation::square::Square;
+
+    const ROOK_DIRECTIONS: [(i8, i8); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+    const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+    fn square(name: &str) -> Square {
+        name.parse().unwrap()
+    }
+
+    fn bitboard(squares: &[&str]) -> Bitboard {
+        squares.iter().fold(Bitboard::EMPTY, |bitboard, name| {
+            bitboard | square(name).bitboard()
+        })
+    }
+
+    /// Slow reference implementation, walking the rays one square at a time.
+    fn walk_rays(square: Square, occupancy: Bitboard, directions: &[(i8, i8)]) -> Bitboard {
+        let mut attacks = Bitboard::EMPTY;
+        for (rank_delta, file_delta) in directions {
+            let mut target = square.offset(*file_delta, *rank_delta);
+            while let Some(attacked) = target {
+                attacks |= attacked.bitboard();
+                if occupancy.contains(attacked) {
+                    break;
+                }
+                target = attacked.offset(*file_delta, *rank_delta);
+            }
+        }
+        attacks
+    }
+
+    #[test]
+    fn knight_attacks_table() {
+        assert_eq!(knight_attacks(square("a1")), bitboard(&["b3", "c2"]));
+        assert_eq!(
+            knight_attacks(square("g7")),
+            bitboard(&["e8", "e6", "f5", "h5"])
+        );
+        assert_eq!(knight_attacks(square("d4")).count(), 8);
+    }
+
+    #[test]
+    fn king_attacks_table() {
+        assert_eq!(king_attacks(square("h8")), bitboard(&["g8", "g7", "h7"]));
+        assert_eq!(
+            king_attacks(square("a4")),
+            bitboard(&["a5", "b5", "b4", "b3", "a3"])
+        );
+        assert_eq!(king_attacks(square("e4")).count(), 8);
+    }
+
+    #[test]
+    fn pawn_attacks_table() {
+        assert_eq!(
+            pawn_attacks(square("e4"), Color::White),
+            bitboard(&["d5", "f5"])
+        );
+        assert_eq!(
+            pawn_attacks(square("e4"), Color::Black),
+            bitboard(&["d3", "f3"])
+        );
+        assert_eq!(pawn_attacks(square("a2"), Color::White), bitboard(&["b3"]));
+        assert_eq!(pawn_attacks(square("h7"), Color::Black), bitboard(&["g6"]));
+        assert_eq!(pawn_attacks(square("c8"), Color::White), Bitboard::EMPTY);
+    }
+
+    #[test]
+    fn between_aligned_squares() {
+        assert_eq!(between(square("a1"), square("a4")), bitboard(&["a2", "a3"]));
+        assert_eq!(between(square("h8"), square("e5")), bitboard(&["g7", "f6"]));
+        assert_eq!(between(square("b6"), square("d4")), bitboard(&["c5"]));
+        assert_eq!(between(square("c3"), square("d3")), Bitboard::EMPTY);
+        assert_eq!(between(square("a1"), square("b3")), Bitboard::EMPTY);
+    }
+
+    #[test]
+    fn line_through_aligned_squares() {
+        assert_eq!(
+            line(square("c1"), square("c5")),
+            Bitboard(0x0404_0404_0404_0404)
+        );
+        assert_eq!(
+            line(square("b2"), square("e5")),
+            Bitboard(0x8040_2010_0804_0201)
+        );
+        assert_eq!(
+            line(square("e5"), square("b2")),
+            Bitboard(0x8040_2010_0804_0201)
+        );
+        assert_eq!(line(square("a1"), square("b3")), Bitboard::EMPTY);
+    }
+
+    #[test]
+    fn empty_board() {
+        assert_eq!(rook_attacks(square("a1"), Bitboard::EMPTY).count(), 14);
+        assert_eq!(rook_attacks(square("e4"), Bitboard::EMPTY).count(), 14);
+        assert_eq!(bishop_attacks(square("a1"), Bitboard::EMPTY).count(), 7);
+        assert_eq!(bishop_attacks(square("d4"), Bitboard::EMPTY).count(), 13);
+        assert_eq!(queen_attacks(square("d4"), Bitboard::EMPTY).count(), 27);
+    }
+
+    #[test]
+    fn blockers_are_attacked() {
+        let occupancy = bitboard(&["d6", "f4", "d1", "b2", "g7"]);
+        assert_eq!(
+            rook_attacks(square("d4"), occupancy),
+            bitboard(&["d5", "d6", "e4", "f4", "d3", "d2", "d1", "c4", "b4", "a4"])
+        );
+        assert_eq!(
+            bishop_attacks(square("d4"), occupancy),
+            bitboard(&[
+                "e5", "f6", "g7", "c3", "b2", "e3", "f2", "g1", "c5", "b6", "a7"
+            ])
+        );
+    }
+
+    #[test]
+    fn own_square_does_not_matter() {
+        let occupancy = bitboard(&["e2", "e7"]);
+        assert_eq!(
+            rook_attacks(square("e4"), occupancy),
+            rook_attacks(square("e4"), occupancy | bitboard(&["e4"]))
+        );
+    }
+
+    #[test]
+    fn random_occupancies() {
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..2000 {
+            // Sparse and dense boards both show up.
+            let occupancy = Bitboard(match next() % 3 {
+                0 => next() & next() & next(),
+                1 => next() & next(),
+                _ => next(),
+            });
+            for square in Square::ALL {
+                assert_eq!(
+                    rook_attacks(square, occupancy),
+                    walk_rays(square, occupancy, &ROOK_DIRECTIONS)
+                );
+                assert_eq!(
+                    bishop_attacks(square, occupancy),
+                    walk_rays(square, occupancy, &BISHOP_DIRECTIONS)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use board_representation::chess_board::{CastlingSide, ChessBoard};
+use board_representation::chess_piece::{Color, PieceKind};
+use board_representation::square::{Rank, Square};
+use std::collections::HashSet;
+
+fn back_rank(board: &ChessBoard) -> String {
+    board.to_fen().split('/').next_back().unwrap()[..8].to_string()
+}
+
+#[cfg(test)]
+mod chess960 {
+    use super::*;
+
+    #[test]
+    fn standard_position() {
+        assert_eq!(ChessBoard::chess960(518), Some(ChessBoard::default()));
+    }
+
+    #[test]
+    fn numbering() {
+        let board = ChessBoard::chess960(0).unwrap();
+        assert_eq!(
+            board.to_fen(),
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1"
+        );
+        assert_eq!(
+            board.to_shredder_fen(),
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w HFhf - 0 1"
+        );
+        assert_eq!(
+            board.castling_rook_square(Color::White, CastlingSide::Queenside),
+            Square::F1
+        );
+        assert_eq!(back_rank(&ChessBoard::chess960(959).unwrap()), "RKRNNQBB");
+        assert_eq!(back_rank(&ChessBoard::chess960(1).unwrap()), "BQNBNRKR");
+        assert_eq!(ChessBoard::chess960(960), None);
+    }
+
+    #[test]
+    fn every_start_position() {
+        let mut back_ranks = HashSet::new();
+        for index in 0..960 {
+            let board = ChessBoard::chess960(index).unwrap();
+            assert_eq!(board.validate_chess960(), Ok(()), "{}", index);
+            assert_eq!(board.hash(), board.compute_hash());
+
+            let bishops = board.bishops & Rank::First.bitboard();
+            let bishop_colors = bishops
+                .into_iter()
+                .map(|square| (square.file().index() + square.rank().index()) % 2)
+                .collect::<HashSet<_>>();
+            assert_eq!(bishop_colors.len(), 2, "{}", index);
+            assert_eq!(
+                board
+                    .maybe_piece_at(
+                        board.castling_rook_square(Color::Black, CastlingSide::Kingside)
+                    )
+                    .map(|piece| piece.kind),
+                Some(PieceKind::Rook)
+            );
+            back_ranks.insert(back_rank(&board));
+        }
+        assert_eq!(back_ranks.len(), 960);
+    }
+}
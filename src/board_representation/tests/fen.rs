@@ -0,0 +1,414 @@
+#[cfg(test)]
+mod fen {
+    use board_representation::chess_board::{CastlingSide, ChessBoard};
+    use board_representation::chess_piece::Color;
+    use board_representation::square::{File, Square};
+    use std::str::FromStr;
+
+    const INITIAL_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    fn assert_round_trip(fen: &str) {
+        let board = ChessBoard::from_str(fen).unwrap();
+        assert_eq!(board.to_fen(), fen);
+        assert_eq!(ChessBoard::from_str(&board.to_fen()).unwrap(), board);
+    }
+
+    #[test]
+    fn initial_position_to_fen() {
+        assert_eq!(ChessBoard::default().to_fen(), INITIAL_POSITION);
+    }
+
+    #[test]
+    fn initial_position_from_fen() {
+        assert_eq!(
+            ChessBoard::from_str(INITIAL_POSITION).unwrap(),
+            ChessBoard::default()
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_round_trip("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+        assert_round_trip("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1");
+        assert_round_trip("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8");
+        assert_round_trip("r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1");
+        assert_round_trip(
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        );
+    }
+
+    #[test]
+    fn partial_castling_rights() {
+        assert_round_trip("r3k2r/8/8/8/8/8/8/R3K2R w Kq - 3 40");
+        assert_round_trip("r3k2r/8/8/8/8/8/8/R3K2R b Qk - 99 120");
+    }
+
+    #[test]
+    fn x_fen_castling_rights() {
+        // The white rook castling kingside is not the outermost one, so it is named by its file.
+        let fen = "rn2k1r1/ppp1pp1p/3p2p1/5bn1/P7/2N2B2/1PPPPP2/2BNK1RR w Gkq - 4 11";
+        let board = ChessBoard::from_str(fen).unwrap();
+        assert_eq!(
+            board.castling_rook_file(Color::White, CastlingSide::Kingside),
+            File::G
+        );
+        assert!(!board.can_castle(Color::White, CastlingSide::Queenside));
+        assert_eq!(
+            board.castling_rook_file(Color::Black, CastlingSide::Kingside),
+            File::G
+        );
+        assert_eq!(
+            board.castling_rook_file(Color::Black, CastlingSide::Queenside),
+            File::A
+        );
+        assert_round_trip(fen);
+        assert_eq!(
+            board.to_shredder_fen(),
+            "rn2k1r1/ppp1pp1p/3p2p1/5bn1/P7/2N2B2/1PPPPP2/2BNK1RR w Gga - 4 11"
+        );
+    }
+
+    #[test]
+    fn shredder_fen_castling_rights() {
+        let fen = "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+        let board = ChessBoard::from_str(fen).unwrap();
+        assert_eq!(board.to_shredder_fen(), fen);
+        assert_eq!(
+            board.to_fen(),
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9"
+        );
+        assert_eq!(ChessBoard::from_str(&board.to_fen()).unwrap(), board);
+        assert_eq!(
+            board.castling_rook_square(Color::Black, CastlingSide::Queenside),
+            Square::F8
+        );
+        assert_eq!(
+            ChessBoard::default().to_shredder_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1"
+        );
+    }
+
+    #[test]
+    fn en_passant_target_square() {
+        let fen = "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2";
+        let board = ChessBoard::from_str(fen).unwrap();
+        assert_eq!(board.en_passant_target_square(), Some(Square::E6));
+        assert_round_trip(fen);
+
+        let fen = "rnbqkbnr/pppppppp/8/8/P7/8/1PPPPPPP/RNBQKBNR b KQkq a3 0 1";
+        let board = ChessBoard::from_str(fen).unwrap();
+        assert_eq!(board.en_passant_target_square(), Some(Square::A3));
+        assert_round_trip(fen);
+    }
+
+    #[test]
+    fn no_en_passant_target_square() {
+        let board = ChessBoard::from_str(INITIAL_POSITION).unwrap();
+        assert_eq!(board.en_passant_target_square(), None);
+    }
+
+    #[test]
+    fn move_counters() {
+        let board = ChessBoard::from_str("8/8/8/kKP5/8/8/8/8 w - - 42 73").unwrap();
+        assert_eq!(board.halfmove_clock(), 42);
+        assert_eq!(board.fullmove_number(), 73);
+    }
+
+    #[test]
+    fn missing_move_counters() {
+        let board = ChessBoard::from_str("8/8/8/kKP5/8/8/8/8 w - -  ").unwrap();
+        assert_eq!(board.halfmove_clock(), 0);
+        assert_eq!(board.fullmove_number(), 1);
+        assert_eq!(board.to_fen(), "8/8/8/kKP5/8/8/8/8 w - - 0 1");
+    }
+
+    mod errors {
+        use super::*;
+        use board_representation::fen_error::{FenError, FenField};
+
+        fn error(fen: &str) -> FenError {
+            ChessBoard::from_str(fen).unwrap_err()
+        }
+
+        #[test]
+        fn wrong_field_count() {
+            assert_eq!(error(""), FenError::WrongFieldCount { found: 0 });
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8 w KQkq"),
+                FenError::WrongFieldCount { found: 3 }
+            );
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8 w - - 0 1 extra"),
+                FenError::WrongFieldCount { found: 7 }
+            );
+        }
+
+        #[test]
+        fn wrong_rank_count() {
+            assert_eq!(
+                error("8/8/8/8/8/8/8 w - - 0 1"),
+                FenError::WrongRankCount {
+                    found: 7,
+                    position: 13
+                }
+            );
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8/8 w - - 0 1"),
+                FenError::WrongRankCount {
+                    found: 9,
+                    position: 15
+                }
+            );
+        }
+
+        #[test]
+        fn invalid_piece() {
+            let error = error("rnbqkbnr/ppppxppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+            assert_eq!(
+                error,
+                FenError::InvalidPiece {
+                    found: 'x',
+                    position: 13
+                }
+            );
+            assert_eq!(error.field(), Some(FenField::PiecePlacement));
+            assert_eq!(error.position(), Some(13));
+        }
+
+        #[test]
+        fn digits_out_of_range() {
+            assert_eq!(
+                error("9/8/8/8/8/8/8/8 w - - 0 1"),
+                FenError::InvalidPiece {
+                    found: '9',
+                    position: 0
+                }
+            );
+            assert_eq!(
+                error("0/8/8/8/8/8/8/8 w - - 0 1"),
+                FenError::InvalidPiece {
+                    found: '0',
+                    position: 0
+                }
+            );
+        }
+
+        #[test]
+        fn rank_too_long() {
+            assert_eq!(
+                error("8/8/8/4P4/8/8/8/8 w - - 0 1"),
+                FenError::RankTooLong {
+                    rank: 5,
+                    position: 8
+                }
+            );
+            assert_eq!(
+                error("8/8/8/8/8/8/8/RNBQKBNRR w - - 0 1"),
+                FenError::RankTooLong {
+                    rank: 1,
+                    position: 22
+                }
+            );
+            assert_eq!(
+                error("8/8/8/8/8/8/8/71P w - - 0 1"),
+                FenError::RankTooLong {
+                    rank: 1,
+                    position: 16
+                }
+            );
+        }
+
+        #[test]
+        fn rank_too_short() {
+            assert_eq!(
+                error("8/7/8/8/8/8/8/8 w - - 0 1"),
+                FenError::RankTooShort {
+                    rank: 7,
+                    position: 3
+                }
+            );
+            assert_eq!(
+                error("8/8/8/8/8/8/8/7 w - - 0 1"),
+                FenError::RankTooShort {
+                    rank: 1,
+                    position: 15
+                }
+            );
+        }
+
+        #[test]
+        fn invalid_side_to_move() {
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8 white - - 0 1"),
+                FenError::InvalidSideToMove {
+                    found: "white".to_string(),
+                    position: 16
+                }
+            );
+        }
+
+        #[test]
+        fn invalid_castling_rights() {
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8 w KQx - 0 1"),
+                FenError::InvalidCastlingRights {
+                    found: 'x',
+                    position: 20
+                }
+            );
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8 w KK - 0 1"),
+                FenError::InvalidCastlingRights {
+                    found: 'K',
+                    position: 19
+                }
+            );
+            assert_eq!(
+                error("4k3/8/8/8/8/8/8/4K2R w E - 0 1"),
+                FenError::InvalidCastlingRights {
+                    found: 'E',
+                    position: 23
+                }
+            );
+        }
+
+        #[test]
+        fn invalid_en_passant_target_square() {
+            for square in ["e4", "i3", "e", "e33", "E3"] {
+                let fen = format!("8/8/8/8/8/8/8/8 w - {} 0 1", square);
+                assert_eq!(
+                    error(&fen),
+                    FenError::InvalidEnPassantTargetSquare {
+                        found: square.to_string(),
+                        position: 20
+                    }
+                );
+            }
+        }
+
+        #[test]
+        fn invalid_move_counters() {
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8 w - - -1 1"),
+                FenError::InvalidHalfmoveClock {
+                    found: "-1".to_string(),
+                    position: 22
+                }
+            );
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8 w - - 0 one"),
+                FenError::InvalidFullmoveNumber {
+                    found: "one".to_string(),
+                    position: 24
+                }
+            );
+        }
+
+        #[test]
+        fn positions_count_characters() {
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8 w - - 0 1".replace('w', "é").as_str()),
+                FenError::InvalidSideToMove {
+                    found: "é".to_string(),
+                    position: 16
+                }
+            );
+            assert_eq!(
+                error("\u{2003}8/8/8/8/8/8/8/8 w - é 0 1"),
+                FenError::InvalidEnPassantTargetSquare {
+                    found: "é".to_string(),
+                    position: 21
+                }
+            );
+        }
+
+        #[test]
+        fn display() {
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8 w - - 0 1 2").to_string(),
+                "Invalid FEN, expected 4 to 6 fields, found 7"
+            );
+            assert_eq!(
+                error("8/8/8/8/8/8/8/8 w - e5 0 1").to_string(),
+                "Invalid FEN en passant target square at position 20, \
+                 expected '-' or a square on the third or sixth rank, found 'e5'"
+            );
+        }
+    }
+
+    mod arbitrary_input {
+        use super::*;
+
+        const ALPHABET: [char; 32] = [
+            'p', 'n', 'b', 'r', 'q', 'k', 'P', 'N', 'B', 'R', 'Q', 'K', '0', '1', '3', '6', '8',
+            '9', '/', ' ', '-', 'w', 'a', 'e', 'h', 'x', '\t', 'é', '♟', '\u{2003}', '\0', '+',
+        ];
+
+        /// Small xorshift generator, so that failures are reproducible.
+        struct Random(u64);
+
+        impl Random {
+            fn next(&mut self, bound: usize) -> usize {
+                self.0 ^= self.0 << 13;
+                self.0 ^= self.0 >> 7;
+                self.0 ^= self.0 << 17;
+                (self.0 % bound as u64) as usize
+            }
+        }
+
+        fn mutate(fen: &str, random: &mut Random) -> String {
+            let mut chars = fen.chars().collect::<Vec<char>>();
+            for _ in 0..=random.next(3) {
+                let c = ALPHABET[random.next(ALPHABET.len())];
+                let index = random.next(chars.len() + 1);
+                match random.next(3) {
+                    0 => chars.insert(index, c),
+                    1 if index < chars.len() => chars[index] = c,
+                    _ if index < chars.len() => {
+                        chars.remove(index);
+                    }
+                    _ => {}
+                }
+            }
+            chars.into_iter().collect()
+        }
+
+        /// Parsing never panics and whatever is accepted survives the round-trip.
+        fn check(fen: &str) {
+            if let Ok(board) = ChessBoard::from_str(fen) {
+                assert_eq!(
+                    ChessBoard::from_str(&board.to_fen()).unwrap(),
+                    board,
+                    "{}",
+                    fen
+                );
+            }
+        }
+
+        #[test]
+        fn mutated_fens() {
+            let mut random = Random(0x2545F4914F6CDD1D);
+            let fens = [
+                INITIAL_POSITION,
+                "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+                "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+            ];
+            for _ in 0..20000 {
+                let fen = fens[random.next(fens.len())];
+                check(&mutate(fen, &mut random));
+            }
+        }
+
+        #[test]
+        fn random_strings() {
+            let mut random = Random(0x9E3779B97F4A7C15);
+            for _ in 0..20000 {
+                let length = random.next(80);
+                let fen = (0..length)
+                    .map(|_| ALPHABET[random.next(ALPHABET.len())])
+                    .collect::<String>();
+                check(&fen);
+            }
+        }
+    }
+}
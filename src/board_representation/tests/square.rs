@@ -0,0 +1,106 @@
+use board_representation::bitboard::Bitboard;
+use board_representation::chess_piece::Color;
+use board_representation::square::{File, Rank, Square, SquareParseError};
+
+fn squares(bitboard: Bitboard) -> Vec<String> {
+    bitboard
+        .into_iter()
+        .map(|square| square.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod square {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!("e4".parse(), Ok(Square::E4));
+        assert_eq!("a1".parse(), Ok(Square::A1));
+        assert_eq!("h8".parse(), Ok(Square::H8));
+        assert_eq!(Square::G7.to_string(), "g7");
+        assert_eq!(format!("{:?}", Square::G7), "G7");
+        for square in Square::ALL {
+            assert_eq!(square.to_string().parse(), Ok(square));
+        }
+        for invalid in ["", "e", "e9", "i4", "E4", "e44", "4e"] {
+            assert_eq!(
+                invalid.parse::<Square>(),
+                Err(SquareParseError {
+                    found: invalid.to_string()
+                })
+            );
+        }
+        assert_eq!("c".parse(), Ok(File::C));
+        assert_eq!("7".parse(), Ok(Rank::Seventh));
+        assert!("x".parse::<File>().is_err());
+        assert!("0".parse::<Rank>().is_err());
+    }
+
+    #[test]
+    fn files_and_ranks() {
+        assert_eq!(Square::new(File::E, Rank::Fourth), Square::E4);
+        assert_eq!(Square::E4.file(), File::E);
+        assert_eq!(Square::E4.rank(), Rank::Fourth);
+        assert_eq!(Square::E4.index(), 28);
+        assert_eq!(Square::from_index(63), Some(Square::H8));
+        assert_eq!(Square::from_index(64), None);
+        assert_eq!(Rank::Second.relative_to(Color::Black), Rank::Seventh);
+        assert_eq!(Rank::Second.relative_to(Color::White), Rank::Second);
+    }
+
+    #[test]
+    fn offsets_stop_at_the_edge() {
+        assert_eq!(Square::E4.offset(1, 2), Some(Square::F6));
+        assert_eq!(Square::E4.offset(-4, -3), Some(Square::A1));
+        assert_eq!(Square::A1.offset(-1, 0), None);
+        assert_eq!(Square::H4.offset(1, 0), None);
+        assert_eq!(Square::H8.offset(0, 1), None);
+        assert_eq!(Square::E2.forward(Color::White), Some(Square::E3));
+        assert_eq!(Square::E2.forward(Color::Black), Some(Square::E1));
+        assert_eq!(Square::E1.forward(Color::Black), None);
+        assert_eq!(Square::E7.backward(Color::Black), Some(Square::E8));
+        assert_eq!(File::A.offset(-1), None);
+        assert_eq!(Rank::Eighth.offset(-7), Some(Rank::First));
+    }
+
+    #[test]
+    fn lines_through_squares() {
+        assert_eq!(squares(File::C.bitboard()).len(), 8);
+        assert!(
+            File::C
+                .bitboard()
+                .into_iter()
+                .all(|sq| sq.file() == File::C)
+        );
+        assert!(
+            Rank::Fifth
+                .bitboard()
+                .into_iter()
+                .all(|sq| sq.rank() == Rank::Fifth)
+        );
+        assert_eq!(squares(Square::F2.diagonal()), ["e1", "f2", "g3", "h4"]);
+        assert_eq!(squares(Square::B6.diagonal()), ["a5", "b6", "c7", "d8"]);
+        assert_eq!(Square::D4.diagonal().count(), 8);
+        assert_eq!(squares(Square::B2.anti_diagonal()), ["c1", "b2", "a3"]);
+        assert_eq!(squares(Square::G7.anti_diagonal()), ["h6", "g7", "f8"]);
+        assert_eq!(Square::E4.anti_diagonal().count(), 8);
+        assert_eq!(squares(Square::H8.anti_diagonal()), ["h8"]);
+    }
+
+    #[test]
+    fn bitboards() {
+        let bitboard = Square::A1.bitboard() | Square::E4.bitboard() | Square::H8.bitboard();
+        assert_eq!(bitboard.count(), 3);
+        assert!(bitboard.contains(Square::E4));
+        assert!(!bitboard.contains(Square::E5));
+        assert_eq!(bitboard.first(), Some(Square::A1));
+        assert_eq!(squares(bitboard), ["a1", "e4", "h8"]);
+        assert_eq!(bitboard.into_iter().len(), 3);
+        assert_eq!(Bitboard::EMPTY.first(), None);
+        assert!(Bitboard::EMPTY.is_empty());
+        assert_eq!(!Bitboard::EMPTY, Bitboard::FULL);
+        assert_eq!(Bitboard::FULL.into_iter().collect::<Vec<_>>(), Square::ALL);
+        assert_eq!(Bitboard::from(Square::E4) << 8, Square::E5.bitboard());
+    }
+}
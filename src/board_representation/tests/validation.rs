@@ -0,0 +1,179 @@
+use board_representation::chess_board::ChessBoard;
+use board_representation::chess_piece::Color;
+use board_representation::fen_error::FenError;
+use board_representation::position_error::{PositionError, PositionViolation};
+use board_representation::square::Square;
+use std::str::FromStr;
+
+fn violations(fen: &str) -> Vec<PositionViolation> {
+    match ChessBoard::from_str(fen).unwrap().validate() {
+        Ok(()) => Vec::new(),
+        Err(PositionError { violations }) => violations,
+    }
+}
+
+#[cfg(test)]
+mod validation {
+    use super::*;
+
+    #[test]
+    fn legal_positions() {
+        assert_eq!(ChessBoard::default().validate(), Ok(()));
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+            "4k3/8/8/8/8/8/8/4K2R w K - 0 1",
+            // The side to move may be in check.
+            "4k3/8/8/8/8/8/8/4K2r w - - 0 1",
+        ] {
+            assert_eq!(violations(fen), vec![], "{}", fen);
+        }
+    }
+
+    #[test]
+    fn kings() {
+        assert_eq!(
+            violations("8/8/8/8/8/8/8/4K3 w - - 0 1"),
+            vec![PositionViolation::MissingKing {
+                color: Color::Black
+            }]
+        );
+        assert_eq!(
+            violations("4k3/8/8/8/8/8/8/K3K3 w - - 0 1"),
+            vec![PositionViolation::TooManyKings {
+                color: Color::White,
+                found: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn pawns_on_back_ranks() {
+        assert_eq!(
+            violations("3Pk3/8/8/8/8/8/8/p3K3 w - - 0 1"),
+            vec![
+                PositionViolation::PawnOnBackRank { square: Square::A1 },
+                PositionViolation::PawnOnBackRank { square: Square::D8 },
+            ]
+        );
+    }
+
+    #[test]
+    fn castling_rights() {
+        // In standard chess the king castles from the e-file only.
+        let fen = "r3k3/8/8/8/8/8/8/R4K1R w KQkq - 0 1";
+        assert_eq!(
+            violations(fen),
+            vec![
+                PositionViolation::InvalidCastlingRight { right: 'K' },
+                PositionViolation::InvalidCastlingRight { right: 'Q' },
+                PositionViolation::InvalidCastlingRight { right: 'k' },
+            ]
+        );
+        // In Chess960 from any file between its rooks.
+        assert_eq!(
+            ChessBoard::from_str(fen).unwrap().validate_chess960(),
+            Err(PositionError {
+                violations: vec![PositionViolation::InvalidCastlingRight { right: 'k' }]
+            })
+        );
+        assert_eq!(
+            violations("4k3/8/8/8/8/8/K7/R6R w KQ - 0 1"),
+            vec![
+                PositionViolation::InvalidCastlingRight { right: 'K' },
+                PositionViolation::InvalidCastlingRight { right: 'Q' },
+            ]
+        );
+        assert_eq!(
+            violations("4k3/8/8/8/8/8/8/R3K1R1 w HQ - 0 1"),
+            vec![PositionViolation::InvalidCastlingRight { right: 'K' }]
+        );
+    }
+
+    #[test]
+    fn en_passant_target_square() {
+        for fen in [
+            // No pawn in front of the square.
+            "4k3/8/8/8/8/8/8/4K3 w - e6 0 1",
+            // Wrong side to move.
+            "4k3/8/8/4p3/8/8/8/4K3 b - e6 0 1",
+            // The pawn could not have come from e7.
+            "4k3/4b3/8/4p3/8/8/8/4K3 w - e6 0 1",
+        ] {
+            assert_eq!(
+                violations(fen),
+                vec![PositionViolation::InvalidEnPassantTargetSquare {
+                    square: ChessBoard::from_str(fen)
+                        .unwrap()
+                        .en_passant_target_square()
+                        .unwrap()
+                }],
+                "{}",
+                fen
+            );
+        }
+    }
+
+    #[test]
+    fn opponent_in_check() {
+        for fen in [
+            "R3k3/8/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/b7/8/8/8/4K3 b - - 0 1",
+            "4k3/8/8/8/8/5n2/8/4K3 b - - 0 1",
+            "4k3/3P4/8/8/8/8/8/4K3 w - - 0 1",
+            "8/8/8/8/8/8/3k4/4K3 w - - 0 1",
+        ] {
+            assert_eq!(
+                violations(fen),
+                vec![PositionViolation::OpponentInCheck],
+                "{}",
+                fen
+            );
+        }
+    }
+
+    #[test]
+    fn every_violation_is_reported() {
+        let error = ChessBoard::from_str("8/8/8/8/8/8/8/P7 w K e3 0 1")
+            .unwrap()
+            .validate()
+            .unwrap_err();
+        assert_eq!(error.violations.len(), 5);
+        assert_eq!(
+            error.to_string(),
+            "Invalid position: White has no king, Black has no king, pawn on a1, on the first or \
+             last rank, castling right 'K' without the king and rook on their starting squares, \
+             en passant target square e3 is not behind a pawn that just moved two squares"
+        );
+    }
+
+    #[test]
+    fn validated_fen() {
+        assert_eq!(
+            ChessBoard::from_fen_validated(
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+            ),
+            Ok(ChessBoard::default())
+        );
+        assert_eq!(
+            ChessBoard::from_fen_validated("4k3/8/8/8/8/8/8/8 w - - 0 1"),
+            Err(FenError::InvalidPosition {
+                error: PositionError {
+                    violations: vec![PositionViolation::MissingKing {
+                        color: Color::White
+                    }]
+                }
+            })
+        );
+        // Rooks named by their files are Chess960 castling rights.
+        let chess960 = "4k3/8/8/8/8/8/8/R4K1R w HA - 0 1";
+        assert!(ChessBoard::from_fen_validated(chess960).is_ok());
+        assert!(ChessBoard::from_fen_validated("4k3/8/8/8/8/8/8/R4K1R w KQ - 0 1").is_err());
+        assert!(
+            ChessBoard::from_chess960_fen_validated("4k3/8/8/8/8/8/8/R4K1R w KQ - 0 1").is_ok()
+        );
+        // The lenient parser still accepts the position.
+        assert!(ChessBoard::from_str("4k3/8/8/8/8/8/8/8 w - - 0 1").is_ok());
+    }
+}
@@ -1 +1,2 @@
 #![allow(clippy::manual_range_contains)]
+pub mod uci;
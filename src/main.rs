@@ -1,3 +1,7 @@
+use chess_engine::uci::UciEngine;
+use std::io;
+
 fn main() {
-    println!("Scamorza is here!");
+    let mut engine = UciEngine::new(io::stdout());
+    engine.run(io::stdin().lock());
 }
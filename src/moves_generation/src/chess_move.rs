@@ -1,170 +1,472 @@
-use board_representation::chess_board::{ChessBoard, Square, SquareIndex};
-use board_representation::chess_piece::{Color, Piece, PieceKind, BLACK_ROOK, WHITE_ROOK};
-use regex::Regex;
+use board_representation::chess_board::{CastlingSide, ChessBoard, SquareContent};
+use board_representation::chess_piece::{Color, Piece, PieceKind};
+use board_representation::square::{Rank, Square};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
 
-#[derive(Copy, Clone, Debug)]
-pub struct Move {
-    pub from: SquareIndex,
-    pub to: SquareIndex,
-    pub promoted_piece_kind: Option<PieceKind>,
+/// A move packed in 16 bits: the origin square in bits 0-5, the destination square in bits
+/// 6-11 and the flags in bits 12-15.
+///
+/// The flags are set by the move generator and say what kind of move it is, so playing a move
+/// does not have to rediscover captures, castling, en passant or double pawn pushes.
+///
+/// Castling is encoded as the king taking its own rook, which tells the rook apart in
+/// Chess960 where the king may not move at all.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Move(u16);
+
+/// The 4 flag bits of a move. Bit 2 marks captures and bit 3 promotions, the two low bits of
+/// a promotion select the promoted piece kind.
+pub type MoveFlags = u8;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveParseError {
+    /// A UCI move has 4 characters, or 5 for promotions.
+    WrongLength {
+        found: usize,
+    },
+    InvalidSquare {
+        found: String,
+    },
+    InvalidPromotion {
+        found: char,
+    },
 }
 
-impl Move {
-    pub fn base_move(from: SquareIndex, to: SquareIndex) -> Self {
-        Self {
+impl fmt::Display for MoveParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveParseError::WrongLength { found } => write!(
+                f,
+                "Invalid UCI string, expected 4 or 5 characters, found {}",
+                found
+            ),
+            MoveParseError::InvalidSquare { found } => write!(
+                f,
+                "Invalid UCI string, expected a square from a1 to h8, found '{}'",
+                found
+            ),
+            MoveParseError::InvalidPromotion { found } => write!(
+                f,
+                "Invalid UCI string, expected a promoted piece kind (qrbn), found '{}'",
+                found
+            ),
+        }
+    }
+}
+
+impl Error for MoveParseError {}
+
+fn parse_square(square: &[char]) -> Result<Square, MoveParseError> {
+    let square = square.iter().collect::<String>();
+    square
+        .parse()
+        .map_err(|_| MoveParseError::InvalidSquare { found: square })
+}
+
+/// A castle written as the king moving to the g- or c-file, or as the king taking its own rook.
+/// A king moving a single square is never taken for a castle.
+fn parse_castle(from: Square, to: Square, board: &ChessBoard) -> Option<Move> {
+    let king = board.maybe_piece_at(from)?;
+    if king.kind != PieceKind::King
+        || from.rank() != to.rank()
+        || from.rank() != Rank::First.relative_to(king.color)
+    {
+        return None;
+    }
+    let side = if to.file() > from.file() {
+        CastlingSide::Kingside
+    } else {
+        CastlingSide::Queenside
+    };
+    let own_rook = Piece {
+        kind: PieceKind::Rook,
+        color: king.color,
+    };
+    let rook = if board.contains_piece_at(to, own_rook) {
+        to
+    } else if from.file().index().abs_diff(to.file().index()) >= 2
+        && to.file() == side.king_destination()
+    {
+        board.castling_rook_square(king.color, side)
+    } else {
+        return None;
+    };
+    Some(Move::castle(from, rook, side))
+}
+
+/// A move in UCI long algebraic notation, e.g. `e2e4` or `e7e8q`, parsed without a board.
+///
+/// The notation says nothing about captures, castling or en passant, [`UciMove::resolve`]
+/// turns it into a [`Move`] with the flags of the position it is played in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UciMove {
+    pub from: Square,
+    pub to: Square,
+    pub promotion: Option<PieceKind>,
+}
+
+impl TryFrom<&str> for UciMove {
+    type Error = MoveParseError;
+
+    fn try_from(uci: &str) -> Result<Self, Self::Error> {
+        let chars = uci.chars().collect::<Vec<char>>();
+        if chars.len() != 4 && chars.len() != 5 {
+            return Err(MoveParseError::WrongLength { found: chars.len() });
+        }
+
+        let from = parse_square(&chars[0..2])?;
+        let to = parse_square(&chars[2..4])?;
+        let promotion = match chars.get(4) {
+            None => None,
+            Some('q') => Some(PieceKind::Queen),
+            Some('r') => Some(PieceKind::Rook),
+            Some('b') => Some(PieceKind::Bishop),
+            Some('n') => Some(PieceKind::Knight),
+            Some(c) => return Err(MoveParseError::InvalidPromotion { found: *c }),
+        };
+        Ok(Self {
             from,
             to,
-            promoted_piece_kind: None,
+            promotion,
+        })
+    }
+}
+
+impl UciMove {
+    /// The move played in `board`, with its flags deduced from the pieces on the board, so
+    /// resolving the UCI string of a generated move gives back the very same move.
+    /// Castling is accepted both as the king moving to the g- or c-file, `e1g1`, and as the
+    /// king taking its own rook, `e1h1`, the Chess960 form.
+    /// The move is not checked for legality.
+    pub fn resolve(self, board: &ChessBoard) -> Move {
+        let UciMove {
+            from,
+            to,
+            promotion,
+        } = self;
+        if let Some(castle) = parse_castle(from, to, board) {
+            return castle;
         }
+
+        let is_capture = board.at(to) != SquareContent::Empty;
+        let moving_kind = board.maybe_piece_at(from).map(|piece| piece.kind);
+        let flags = match (promotion, moving_kind) {
+            (Some(kind), _) if is_capture => Move::PROMOTION_CAPTURE | promotion_bits(kind),
+            (Some(kind), _) => Move::PROMOTION | promotion_bits(kind),
+            (None, _) if is_capture => Move::CAPTURE,
+            (None, Some(PieceKind::Pawn))
+                if from.rank().index().abs_diff(to.rank().index()) == 2 =>
+            {
+                Move::DOUBLE_PAWN_PUSH
+            }
+            (None, Some(PieceKind::Pawn))
+                if from.file() != to.file() && board.en_passant_target_square() == Some(to) =>
+            {
+                Move::EN_PASSANT
+            }
+            (None, _) => Move::QUIET,
+        };
+        Move::new(from, to, flags)
     }
-    pub fn from_uci_string(s: &str) -> Self {
-        let re = Regex::new(r"^[a-h][1-8][a-h][1-8][qrbn]?$").unwrap();
-        if !re.is_match(s) {
-            panic!("Invalid UCI string");
+}
+
+impl fmt::Display for UciMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.from, self.to)?;
+        match self.promotion {
+            Some(PieceKind::Rook) => write!(f, "r"),
+            Some(PieceKind::Knight) => write!(f, "n"),
+            Some(PieceKind::Bishop) => write!(f, "b"),
+            Some(_) => write!(f, "q"),
+            None => Ok(()),
         }
+    }
+}
 
-        let bytes = s.as_bytes();
-        let from_col = (bytes[0] - b'a') as usize;
-        let from_row = (bytes[1] - b'1') as usize;
-        let to_col = (bytes[2] - b'a') as usize;
-        let to_row = (bytes[3] - b'1') as usize;
-        let promoted_piece = if bytes.len() == 4 {
-            None
-        } else {
-            let piece_kind = match bytes[4] {
-                b'q' => PieceKind::Queen,
-                b'r' => PieceKind::Rook,
-                b'b' => PieceKind::Bishop,
-                b'n' => PieceKind::Knight,
-                _ => panic!("Invalid UCI string, unknown promoted piece kind"),
-            };
-            Some(piece_kind)
+fn promotion_bits(promoted_piece_kind: PieceKind) -> MoveFlags {
+    match promoted_piece_kind {
+        PieceKind::Knight => 0,
+        PieceKind::Bishop => 1,
+        PieceKind::Rook => 2,
+        PieceKind::Queen => 3,
+        _ => panic!(
+            "Invalid move, promoted piece cannot be of kind {:?}",
+            promoted_piece_kind
+        ),
+    }
+}
+
+impl Move {
+    pub const QUIET: MoveFlags = 0;
+    pub const DOUBLE_PAWN_PUSH: MoveFlags = 1;
+    pub const KING_CASTLE: MoveFlags = 2;
+    pub const QUEEN_CASTLE: MoveFlags = 3;
+    pub const CAPTURE: MoveFlags = 4;
+    pub const EN_PASSANT: MoveFlags = 5;
+    /// Combined with the promoted piece kind: knight 0, bishop 1, rook 2, queen 3.
+    pub const PROMOTION: MoveFlags = 8;
+    pub const PROMOTION_CAPTURE: MoveFlags = Self::PROMOTION | Self::CAPTURE;
+
+    /// All bits set to 0, a1a1. Never generated, it marks the absence of a move.
+    pub const NULL: Move = Move(0);
+
+    pub fn new(from: Square, to: Square, flags: MoveFlags) -> Self {
+        debug_assert!(flags < 16);
+        Self(from.index() as u16 | (to.index() as u16) << 6 | (flags as u16) << 12)
+    }
+
+    pub fn quiet(from: Square, to: Square) -> Self {
+        Self::new(from, to, Self::QUIET)
+    }
+
+    pub fn capture(from: Square, to: Square) -> Self {
+        Self::new(from, to, Self::CAPTURE)
+    }
+
+    pub fn promotion(from: Square, to: Square, promoted_piece_kind: PieceKind) -> Self {
+        Self::new(
+            from,
+            to,
+            Self::PROMOTION | promotion_bits(promoted_piece_kind),
+        )
+    }
+
+    pub fn promotion_capture(from: Square, to: Square, promoted_piece_kind: PieceKind) -> Self {
+        Self::new(
+            from,
+            to,
+            Self::PROMOTION_CAPTURE | promotion_bits(promoted_piece_kind),
+        )
+    }
+
+    /// The king takes its own castling rook on `rook`.
+    pub fn castle(king: Square, rook: Square, side: CastlingSide) -> Self {
+        let flags = match side {
+            CastlingSide::Kingside => Self::KING_CASTLE,
+            CastlingSide::Queenside => Self::QUEEN_CASTLE,
         };
-        Move {
-            from: (from_row * 8 + from_col) as u8,
-            to: (to_row * 8 + to_col) as u8,
-            promoted_piece_kind: promoted_piece,
+        Self::new(king, rook, flags)
+    }
+
+    pub fn from(self) -> Square {
+        Square::ALL[(self.0 & 0x3F) as usize]
+    }
+
+    pub fn to(self) -> Square {
+        Square::ALL[(self.0 >> 6 & 0x3F) as usize]
+    }
+
+    /// The square the moving piece lands on: `to`, except for castling where it is the g- or
+    /// c-file square the king goes to.
+    pub fn destination(self) -> Square {
+        match self.castling_side() {
+            None => self.to(),
+            Some(side) => Square::new(side.king_destination(), self.from().rank()),
         }
     }
 
+    pub fn flags(self) -> MoveFlags {
+        (self.0 >> 12) as MoveFlags
+    }
+
+    /// The packed representation, e.g. to store the move in a table.
+    pub fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    pub fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    pub fn promoted_piece_kind(self) -> Option<PieceKind> {
+        if !self.is_promotion() {
+            return None;
+        }
+        Some(match self.flags() & 3 {
+            0 => PieceKind::Knight,
+            1 => PieceKind::Bishop,
+            2 => PieceKind::Rook,
+            _ => PieceKind::Queen,
+        })
+    }
+
+    /// En passant included.
+    pub fn is_capture(self) -> bool {
+        self.flags() & Self::CAPTURE != 0
+    }
+
+    pub fn is_promotion(self) -> bool {
+        self.flags() & Self::PROMOTION != 0
+    }
+
+    pub fn is_en_passant(self) -> bool {
+        self.flags() == Self::EN_PASSANT
+    }
+
+    pub fn is_castling(self) -> bool {
+        self.flags() == Self::KING_CASTLE || self.flags() == Self::QUEEN_CASTLE
+    }
+
+    pub fn castling_side(self) -> Option<CastlingSide> {
+        match self.flags() {
+            Self::KING_CASTLE => Some(CastlingSide::Kingside),
+            Self::QUEEN_CASTLE => Some(CastlingSide::Queenside),
+            _ => None,
+        }
+    }
+
+    pub fn is_double_pawn_push(self) -> bool {
+        self.flags() == Self::DOUBLE_PAWN_PUSH
+    }
+
+    /// Parses a move in UCI long algebraic notation, e.g. `e2e4` or `e7e8q`, played in `board`.
+    /// See [`UciMove`] for parsing without a board.
+    pub fn from_uci_string(s: &str, board: &ChessBoard) -> Result<Self, MoveParseError> {
+        UciMove::try_from(s).map(|uci_move| uci_move.resolve(board))
+    }
+
+    /// The UCI string of this move, with castling written as the king moving to its square.
     pub fn to_uci_string(self) -> String {
-        let from_row = char::from(self.from / 8 + b'1');
-        let from_col = char::from(self.from % 8 + b'a');
-        let to_row = char::from(self.to / 8 + b'1');
-        let to_col = char::from(self.to % 8 + b'a');
-        match self.promoted_piece_kind {
-            None => format!("{}{}{}{}", from_col, from_row, to_col, to_row),
+        self.uci_string(self.destination())
+    }
+
+    /// The UCI string of this move for engines in `UCI_Chess960` mode, with castling written
+    /// as the king taking its own rook.
+    pub fn to_chess960_uci_string(self) -> String {
+        self.uci_string(self.to())
+    }
+
+    fn uci_string(self, to: Square) -> String {
+        let from = self.from();
+        match self.promoted_piece_kind() {
+            None => format!("{}{}", from, to),
             Some(piece_kind) => {
                 let piece_kind_char = match piece_kind {
                     PieceKind::Rook => 'r',
                     PieceKind::Knight => 'n',
                     PieceKind::Bishop => 'b',
-                    PieceKind::Queen => 'q',
-                    _ => panic!(
-                        "Invalid move, promoted piece cannot be of kind {:?}",
-                        piece_kind
-                    ),
+                    _ => 'q',
                 };
-                format!(
-                    "{}{}{}{}{}",
-                    from_col, from_row, to_col, to_row, piece_kind_char
-                )
+                format!("{}{}{}", from, to, piece_kind_char)
             }
         }
     }
 
-    fn castle_invalidation(&self, board: &mut ChessBoard) {
-        match self.from {
-            0 => board.set_white_castle_queenside(false),
-            7 => board.set_white_castle_kingside(false),
-            56 => board.set_black_castle_queenside(false),
-            63 => board.set_black_castle_kingside(false),
-            4 => {
-                board.set_white_castle_kingside(false);
-                board.set_white_castle_queenside(false);
-            }
-            60 => {
-                board.set_black_castle_kingside(false);
-                board.set_black_castle_queenside(false);
-            }
-            _ => {}
-        }
-
-        match self.to {
-            0 => board.set_white_castle_queenside(false),
-            7 => board.set_white_castle_kingside(false),
-            56 => board.set_black_castle_queenside(false),
-            63 => board.set_black_castle_kingside(false),
-            _ => {}
-        }
-    }
-
-    fn move_rook_when_castling(&self, board: &mut ChessBoard, moving_piece: &Piece) {
-        if moving_piece.kind != PieceKind::King {
+    fn castle_invalidation(&self, board: &mut ChessBoard, moving_piece: &Piece) {
+        // Kings and rooks keeping a castling right are on their back rank.
+        let back_ranks = Rank::First.bitboard() | Rank::Eighth.bitboard();
+        if board.castling_rights() == 0
+            || !(back_ranks.contains(self.from()) || back_ranks.contains(self.to()))
+        {
             return;
         }
-
-        if self.from == 4 {
-            if self.to == 2 {
-                board.set_at(3, Square::Occupied(WHITE_ROOK));
-                board.set_at(0, Square::Empty)
-            } else if self.to == 6 {
-                board.set_at(5, Square::Occupied(WHITE_ROOK));
-                board.set_at(7, Square::Empty)
+        if moving_piece.kind == PieceKind::King {
+            for side in CastlingSide::ALL {
+                board.set_can_castle(moving_piece.color, side, false);
             }
-        } else if self.from == 60 {
-            if self.to == 58 {
-                board.set_at(59, Square::Occupied(BLACK_ROOK));
-                board.set_at(56, Square::Empty)
-            } else if self.to == 62 {
-                board.set_at(61, Square::Occupied(BLACK_ROOK));
-                board.set_at(63, Square::Empty)
+        }
+        // A rook moving away or captured on its starting square.
+        for color in [Color::White, Color::Black] {
+            for side in CastlingSide::ALL {
+                let rook = board.castling_rook_square(color, side);
+                if board.can_castle(color, side) && (self.from() == rook || self.to() == rook) {
+                    board.set_can_castle(color, side, false);
+                }
             }
         }
     }
 
-    fn remove_piece_after_en_passant(&self, board: &mut ChessBoard, moving_piece: &Piece) {
-        if let (PieceKind::Pawn, Some(en_passant_square)) =
-            (moving_piece.kind, board.en_passant_target_square())
-        {
-            if self.to == en_passant_square {
-                let square_to_clear = match moving_piece.color {
-                    Color::White => en_passant_square - 8,
-                    Color::Black => en_passant_square + 8,
-                };
-                board.set_at(square_to_clear, Square::Empty)
-            }
-        }
+    /// Squares the rook moves from and to when this move is a castle.
+    pub(crate) fn castling_rook_squares(&self) -> Option<(Square, Square)> {
+        self.castling_side().map(|side| {
+            (
+                self.to(),
+                Square::new(side.rook_destination(), self.to().rank()),
+            )
+        })
     }
 
-    fn is_double_pawn_move(&self, moving_piece: &Piece) -> bool {
-        if moving_piece.kind != PieceKind::Pawn {
-            return false;
-        }
+    /// Square of the pawn captured en passant, which is not the destination of the move.
+    pub(crate) fn en_passant_capture_square(&self) -> Square {
+        Square::new(self.to().file(), self.from().rank())
+    }
 
-        self.from.abs_diff(self.to) > 9
+    fn remove_piece_after_en_passant(&self, board: &mut ChessBoard) {
+        if self.is_en_passant() {
+            board.set_at(self.en_passant_capture_square(), SquareContent::Empty)
+        }
     }
 
     fn update_en_passant_target_square(&self, board: &mut ChessBoard, moving_piece: &Piece) {
-        if self.is_double_pawn_move(moving_piece) {
-            board.set_en_passant_target_square(Some((self.from + self.to) / 2))
+        if self.is_double_pawn_push() {
+            board.set_en_passant_target_square(self.to().backward(moving_piece.color))
         } else {
             board.set_en_passant_target_square(None)
         }
     }
 
+    fn update_clocks(&self, board: &mut ChessBoard, moving_piece: &Piece) {
+        if moving_piece.kind == PieceKind::Pawn || self.is_capture() {
+            board.set_halfmove_clock(0);
+        } else {
+            board.set_halfmove_clock(board.halfmove_clock().saturating_add(1));
+        }
+
+        if moving_piece.color == Color::Black {
+            board.set_fullmove_number(board.fullmove_number().saturating_add(1));
+        }
+    }
+
     pub fn move_piece(&self, board: &mut ChessBoard) {
-        let moving_piece = match board.at(self.from) {
-            Square::Occupied(piece) => piece,
-            Square::Empty => panic!("Invalid move: Cannot move from empty square"),
+        board.make_move(*self);
+    }
+}
+
+/// State overwritten by `make_move` that cannot be recomputed from the move itself.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UndoInfo {
+    pub captured_piece: Option<Piece>,
+    pub castling_rights: u8,
+    pub en_passant_target_square: Option<Square>,
+    pub halfmove_clock: u16,
+}
+
+pub trait MakeMove {
+    /// Plays `mov`, returning what `unmake_move` needs to take it back.
+    fn make_move(&mut self, mov: Move) -> UndoInfo;
+
+    /// Takes back `mov`, which must be the last move played with `make_move`.
+    fn unmake_move(&mut self, mov: Move, undo_info: UndoInfo);
+}
+
+impl MakeMove for ChessBoard {
+    fn make_move(&mut self, mov: Move) -> UndoInfo {
+        let moving_piece = self.piece_at_source_or_panic(mov.from());
+        let captured_piece = if mov.is_en_passant() {
+            Some(Piece {
+                kind: PieceKind::Pawn,
+                color: !moving_piece.color,
+            })
+        } else if mov.is_castling() {
+            None
+        } else {
+            self.maybe_piece_at(mov.to())
+        };
+        let undo_info = UndoInfo {
+            captured_piece,
+            castling_rights: self.castling_rights(),
+            en_passant_target_square: self.en_passant_target_square(),
+            halfmove_clock: self.halfmove_clock(),
         };
 
-        self.castle_invalidation(board);
-        self.move_rook_when_castling(board, &moving_piece);
-        self.remove_piece_after_en_passant(board, &moving_piece);
-        self.update_en_passant_target_square(board, &moving_piece);
+        mov.update_clocks(self, &moving_piece);
+        mov.castle_invalidation(self, &moving_piece);
+        mov.remove_piece_after_en_passant(self);
+        mov.update_en_passant_target_square(self, &moving_piece);
 
-        let promoted_piece = match self.promoted_piece_kind {
+        let promoted_piece = match mov.promoted_piece_kind() {
             None => moving_piece,
             Some(promoted_piece_kind) => Piece {
                 color: moving_piece.color,
@@ -172,14 +474,71 @@ impl Move {
             },
         };
 
-        board.set_at(self.from, Square::Empty);
-        board.set_at(self.to, Square::Occupied(promoted_piece));
+        // In Chess960 the king and the rook may land on each other's starting squares, so both
+        // leave the board before either is put back.
+        self.set_at(mov.from(), SquareContent::Empty);
+        if let Some((rook_from, rook_to)) = mov.castling_rook_squares() {
+            let rook = self.at(rook_from);
+            self.set_at(rook_from, SquareContent::Empty);
+            self.set_at(rook_to, rook);
+        }
+        self.set_at(mov.destination(), SquareContent::Occupied(promoted_piece));
+
+        self.next_turn();
+        undo_info
+    }
+
+    fn unmake_move(&mut self, mov: Move, undo_info: UndoInfo) {
+        self.next_turn();
+        let color = self.current_turn();
+        let moving_piece = if mov.is_promotion() {
+            Piece {
+                kind: PieceKind::Pawn,
+                color,
+            }
+        } else {
+            self.piece_at_source_or_panic(mov.destination())
+        };
+
+        self.set_at(mov.destination(), SquareContent::Empty);
+        if let Some((rook_from, rook_to)) = mov.castling_rook_squares() {
+            let rook = self.at(rook_to);
+            self.set_at(rook_to, SquareContent::Empty);
+            self.set_at(rook_from, rook);
+        }
+        self.set_at(mov.from(), SquareContent::Occupied(moving_piece));
+        if let Some(captured_piece) = undo_info.captured_piece {
+            let capture_square = if mov.is_en_passant() {
+                mov.en_passant_capture_square()
+            } else {
+                mov.to()
+            };
+            self.set_at(capture_square, SquareContent::Occupied(captured_piece));
+        }
+
+        self.set_castling_rights(undo_info.castling_rights);
+        self.set_en_passant_target_square(undo_info.en_passant_target_square);
+        self.set_halfmove_clock(undo_info.halfmove_clock);
+        if color == Color::Black {
+            self.set_fullmove_number(self.fullmove_number() - 1);
+        }
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_uci_string())
+    }
+}
 
-        board.next_turn();
+impl fmt::Debug for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Move({}, flags {:04b})", self, self.flags())
     }
 }
 
-pub fn move_piece_uci(board: &mut ChessBoard, uci: &str) {
-    let mov = Move::from_uci_string(uci);
+pub fn move_piece_uci(board: &mut ChessBoard, uci: &str) -> Result<(), MoveParseError> {
+    let mov = Move::from_uci_string(uci, board)?;
     mov.move_piece(board);
+    Ok(())
 }
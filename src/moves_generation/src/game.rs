@@ -0,0 +1,207 @@
+use crate::chess_move::{MakeMove, Move, UndoInfo};
+use crate::valid_moves::{all_valid_moves, is_check};
+use board_representation::bitboard::Bitboard;
+use board_representation::chess_board::ChessBoard;
+use board_representation::chess_piece::Color;
+use board_representation::zobrist::ZobristHash;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// a1, c1, e1, g1, b2, d2 and so on, a1 being a dark square.
+const DARK_SQUARES: Bitboard = Bitboard(0xAA55_AA55_AA55_AA55);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate {
+        winner: Color,
+    },
+    Stalemate,
+    /// The position occurred three times, either player may claim a draw.
+    ThreefoldRepetition,
+    /// The position occurred five times, the game is drawn.
+    FivefoldRepetition,
+    /// 50 moves without capture nor pawn move, either player may claim a draw.
+    FiftyMoveRule,
+    /// 75 moves without capture nor pawn move, the game is drawn.
+    SeventyFiveMoveRule,
+    /// Neither player can checkmate.
+    InsufficientMaterial,
+}
+
+impl GameStatus {
+    /// Whether the game is over. Claimable draws count as over, an engine always claims them.
+    pub fn is_over(self) -> bool {
+        self != GameStatus::Ongoing
+    }
+
+    pub fn is_draw(self) -> bool {
+        !matches!(self, GameStatus::Ongoing | GameStatus::Checkmate { .. })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IllegalMoveError {
+    pub mov: Move,
+    pub fen: String,
+}
+
+impl fmt::Display for IllegalMoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Illegal move {} in position {}", self.mov, self.fen)
+    }
+}
+
+impl Error for IllegalMoveError {}
+
+/// A game being played: the current position along with the moves that led to it.
+///
+/// Repetitions are detected with the Zobrist hashes of the positions, which include the
+/// side to move and the castling rights. The en passant target square only counts when a
+/// pawn can actually capture en passant.
+#[derive(Clone, Debug)]
+pub struct Game {
+    starting_board: ChessBoard,
+    board: ChessBoard,
+    /// The moves played, with what is needed to take them back.
+    history: Vec<(Move, UndoInfo)>,
+    /// The repetition key of every position of the game, the starting position first.
+    keys: Vec<ZobristHash>,
+    /// Moves taken back with `undo`, the next one to replay last.
+    undone: Vec<Move>,
+}
+
+impl Game {
+    pub fn new(starting_board: ChessBoard) -> Self {
+        Self {
+            starting_board,
+            board: starting_board,
+            history: Vec::new(),
+            keys: vec![repetition_key(&starting_board)],
+            undone: Vec::new(),
+        }
+    }
+
+    pub fn board(&self) -> &ChessBoard {
+        &self.board
+    }
+
+    pub fn starting_board(&self) -> &ChessBoard {
+        &self.starting_board
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> + '_ {
+        self.history.iter().map(|(mov, _)| *mov)
+    }
+
+    /// Plays `mov` if it is legal in the current position. Playing a move forgets the moves
+    /// that could be redone.
+    pub fn play(&mut self, mov: Move) -> Result<(), IllegalMoveError> {
+        if !all_valid_moves(&self.board).contains(&mov) {
+            return Err(IllegalMoveError {
+                mov,
+                fen: self.board.to_fen(),
+            });
+        }
+        self.undone.clear();
+        self.push(mov);
+        Ok(())
+    }
+
+    fn push(&mut self, mov: Move) {
+        let undo_info = self.board.make_move(mov);
+        self.history.push((mov, undo_info));
+        self.keys.push(repetition_key(&self.board));
+    }
+
+    /// Takes back the last move, returns `None` at the start of the game.
+    pub fn undo(&mut self) -> Option<Move> {
+        let (mov, undo_info) = self.history.pop()?;
+        self.board.unmake_move(mov, undo_info);
+        self.keys.pop();
+        self.undone.push(mov);
+        Some(mov)
+    }
+
+    /// Replays the last move taken back, returns `None` if there is none.
+    pub fn redo(&mut self) -> Option<Move> {
+        let mov = self.undone.pop()?;
+        self.push(mov);
+        Some(mov)
+    }
+
+    /// How many times the current position occurred in the game, itself included.
+    pub fn repetitions(&self) -> usize {
+        let current = self.keys[self.keys.len() - 1];
+        // A capture or a pawn move makes the earlier positions impossible to reach again.
+        let reversible_plies = (self.board.halfmove_clock() as usize).min(self.keys.len() - 1);
+        self.keys
+            .iter()
+            .rev()
+            .take(reversible_plies + 1)
+            .step_by(2)
+            .filter(|key| **key == current)
+            .count()
+    }
+
+    pub fn status(&self) -> GameStatus {
+        // Checkmate takes precedence over every draw rule.
+        if all_valid_moves(&self.board).is_empty() {
+            return if is_check(&self.board) {
+                GameStatus::Checkmate {
+                    winner: !self.board.current_turn(),
+                }
+            } else {
+                GameStatus::Stalemate
+            };
+        }
+        let repetitions = self.repetitions();
+        let halfmove_clock = self.board.halfmove_clock();
+        if repetitions >= 5 {
+            GameStatus::FivefoldRepetition
+        } else if halfmove_clock >= 150 {
+            GameStatus::SeventyFiveMoveRule
+        } else if has_insufficient_material(&self.board) {
+            GameStatus::InsufficientMaterial
+        } else if repetitions >= 3 {
+            GameStatus::ThreefoldRepetition
+        } else if halfmove_clock >= 100 {
+            GameStatus::FiftyMoveRule
+        } else {
+            GameStatus::Ongoing
+        }
+    }
+}
+
+/// The hash of the position, ignoring the en passant target square when no en passant
+/// capture is legal: the positions are then the same for the repetition rules.
+fn repetition_key(board: &ChessBoard) -> ZobristHash {
+    if board.en_passant_target_square().is_none()
+        || all_valid_moves(board).iter().any(|mov| mov.is_en_passant())
+    {
+        return board.hash();
+    }
+    let mut board = *board;
+    board.set_en_passant_target_square(None);
+    board.hash()
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new(ChessBoard::default())
+    }
+}
+
+/// King against king and at most one minor piece, or kings and bishops all on squares of
+/// the same color.
+pub fn has_insufficient_material(board: &ChessBoard) -> bool {
+    if !(board.pawns | board.rooks | board.queens).is_empty() {
+        return false;
+    }
+    let minor_pieces = board.knights | board.bishops;
+    minor_pieces.count() <= 1
+        || (board.knights.is_empty()
+            && ((board.bishops & DARK_SQUARES).is_empty()
+                || (board.bishops & !DARK_SQUARES).is_empty()))
+}
@@ -0,0 +1,72 @@
+use crate::moves_generation_utils::attackers;
+use board_representation::attacks::{between, bishop_attacks, line, rook_attacks};
+use board_representation::bitboard::Bitboard;
+use board_representation::chess_board::ChessBoard;
+use board_representation::square::Square;
+
+/// Checks and pins against the king of the side to move, computed once per position so that
+/// only legal moves are generated.
+pub(crate) struct KingSafety {
+    pub(crate) king: Square,
+    /// Opponent pieces giving check.
+    pub(crate) checkers: Bitboard,
+    /// Pieces of the side to move that cannot leave the line between their king and an
+    /// opponent slider.
+    pub(crate) pinned: Bitboard,
+    /// Squares a piece other than the king must move to: every square when not in check,
+    /// the checker and the squares between it and the king in single check, none in double check.
+    pub(crate) check_mask: Bitboard,
+}
+
+impl KingSafety {
+    pub(crate) fn new(board: &ChessBoard) -> Self {
+        let color = board.current_turn();
+        let king = board.find_king(color);
+        let occupancy = board.occupancy();
+        let opponent_pieces = board.pieces_of(!color);
+        let checkers = attackers(board, king, !color, occupancy);
+
+        // Opponent sliders that would attack the king if only opponent pieces were on the board.
+        let snipers = opponent_pieces
+            & (rook_attacks(king, opponent_pieces) & (board.rooks | board.queens)
+                | bishop_attacks(king, opponent_pieces) & (board.bishops | board.queens));
+        let mut pinned = Bitboard::EMPTY;
+        for sniper in snipers {
+            let blockers = between(king, sniper) & occupancy;
+            if blockers.count() == 1 {
+                pinned |= blockers & board.pieces_of(color);
+            }
+        }
+
+        let check_mask = match checkers.first() {
+            None => Bitboard::FULL,
+            Some(checker) if checkers.count() == 1 => checkers | between(king, checker),
+            Some(_) => Bitboard::EMPTY,
+        };
+
+        Self {
+            king,
+            checkers,
+            pinned,
+            check_mask,
+        }
+    }
+
+    pub(crate) fn is_in_check(&self) -> bool {
+        !self.checkers.is_empty()
+    }
+
+    pub(crate) fn is_in_double_check(&self) -> bool {
+        self.checkers.count() > 1
+    }
+
+    /// Squares the piece on `from` can move to without leaving its king in check.
+    /// Does not apply to the king itself, nor to en passant captures.
+    pub(crate) fn legal_targets(&self, from: Square) -> Bitboard {
+        if self.pinned.contains(from) {
+            self.check_mask & line(self.king, from)
+        } else {
+            self.check_mask
+        }
+    }
+}
@@ -1,4 +1,13 @@
+mod king_safety;
 mod moves_generation_utils;
-mod chess_move;
+pub mod chess_move;
+pub mod game;
+pub mod move_list;
+pub mod move_picker;
+pub mod san;
 pub mod valid_moves;
-mod move_directions;
+
+pub use chess_move::Move;
+pub use game::{Game, GameStatus};
+pub use move_list::MoveList;
+pub use move_picker::{MoveHistory, MovePicker};
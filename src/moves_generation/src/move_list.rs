@@ -0,0 +1,160 @@
+use crate::chess_move::Move;
+use std::fmt;
+use std::fmt::Formatter;
+use std::ops::{Deref, DerefMut};
+
+/// No legal chess position has more than 218 moves.
+pub const MAX_MOVES: usize = 256;
+
+/// A fixed-capacity list of moves living on the stack, so that generating moves never
+/// allocates. Derefs to a slice for iteration, indexing and the usual slice methods.
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_MOVES],
+    len: usize,
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        Self {
+            moves: [Move::NULL; MAX_MOVES],
+            len: 0,
+        }
+    }
+
+    /// Panics if the list is full, which cannot happen with the moves of a legal position.
+    pub fn push(&mut self, mov: Move) {
+        self.moves[self.len] = mov;
+        self.len += 1;
+    }
+
+    /// Removes the move at `index` in constant time, replacing it with the last move.
+    pub fn swap_remove(&mut self, index: usize) -> Move {
+        assert!(index < self.len, "Index {} out of bounds", index);
+        let mov = self.moves[index];
+        self.len -= 1;
+        self.moves[index] = self.moves[self.len];
+        mov
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Sorts the moves from the highest to the lowest score, keeping the generation order
+    /// between moves with the same score. `score` is called once per move.
+    pub fn sort_by_score<F>(&mut self, mut score: F)
+    where
+        F: FnMut(&Move) -> i32,
+    {
+        let mut scores = [0; MAX_MOVES];
+        for (index, mov) in self.iter().enumerate() {
+            scores[index] = score(mov);
+        }
+        // Insertion sort: move lists are short, and it needs no extra memory.
+        for i in 1..self.len {
+            let (mov, mov_score) = (self.moves[i], scores[i]);
+            let mut j = i;
+            while j > 0 && scores[j - 1] < mov_score {
+                self.moves[j] = self.moves[j - 1];
+                scores[j] = scores[j - 1];
+                j -= 1;
+            }
+            self.moves[j] = mov;
+            scores[j] = mov_score;
+        }
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [Move] {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for MoveList {}
+
+impl Extend<Move> for MoveList {
+    fn extend<I: IntoIterator<Item = Move>>(&mut self, iter: I) {
+        for mov in iter {
+            self.push(mov);
+        }
+    }
+}
+
+impl FromIterator<Move> for MoveList {
+    fn from_iter<I: IntoIterator<Item = Move>>(iter: I) -> Self {
+        let mut moves = MoveList::new();
+        moves.extend(iter);
+        moves
+    }
+}
+
+pub struct IntoIter {
+    moves: MoveList,
+    index: usize,
+}
+
+impl Iterator for IntoIter {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        let mov = self.moves.get(self.index).copied();
+        self.index += 1;
+        mov
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.moves.len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter {
+            moves: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,214 @@
+use crate::chess_move::Move;
+use crate::move_list::{MAX_MOVES, MoveList};
+use crate::valid_moves::all_valid_moves;
+use board_representation::chess_board::{ChessBoard, SquareContent};
+use board_representation::chess_piece::{Color, PieceKind};
+
+/// Plies for which killer moves are kept, deeper plies have none.
+pub const MAX_PLY: usize = 128;
+
+/// History scores stay within plus or minus this, below the score of countermoves.
+pub const HISTORY_MAX: i32 = 16384;
+
+const HASH_MOVE_SCORE: i32 = 1 << 30;
+const CAPTURE_SCORE: i32 = 1 << 24;
+const FIRST_KILLER_SCORE: i32 = 1 << 22;
+const SECOND_KILLER_SCORE: i32 = FIRST_KILLER_SCORE - 1;
+const COUNTERMOVE_SCORE: i32 = FIRST_KILLER_SCORE - 2;
+
+/// Whether `mov` neither captures nor promotes, the moves ordered by the heuristics of
+/// [`MoveHistory`] rather than by material.
+pub fn is_quiet(mov: Move) -> bool {
+    !mov.is_capture() && !mov.is_promotion()
+}
+
+/// What the search learns about quiet moves from its beta cutoffs, to try them first elsewhere.
+#[derive(Clone)]
+pub struct MoveHistory {
+    /// Two quiet moves per ply that caused a cutoff, the most recent first.
+    killers: [[Move; 2]; MAX_PLY],
+    /// Butterfly table: how often a quiet move of a color caused a cutoff, by depth.
+    history: [[[i32; 64]; 64]; 2],
+    /// The quiet move that refuted each move, indexed by its from and to squares.
+    countermoves: [[Move; 64]; 64],
+}
+
+impl MoveHistory {
+    pub fn new() -> Self {
+        Self {
+            killers: [[Move::NULL; 2]; MAX_PLY],
+            history: [[[0; 64]; 64]; 2],
+            countermoves: [[Move::NULL; 64]; 64],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn killers(&self, ply: usize) -> [Move; 2] {
+        self.killers.get(ply).copied().unwrap_or([Move::NULL; 2])
+    }
+
+    pub fn history(&self, color: Color, mov: Move) -> i32 {
+        self.history[color as usize][mov.from().index() as usize][mov.to().index() as usize]
+    }
+
+    pub fn countermove(&self, previous_move: Move) -> Option<Move> {
+        let mov = self.countermoves[previous_move.from().index() as usize]
+            [previous_move.to().index() as usize];
+        (mov != Move::NULL).then_some(mov)
+    }
+
+    /// Records that the quiet move `mov` of `color` caused a beta cutoff at `ply`, searched
+    /// `depth` plies deep, in reply to `previous_move`. The quiet moves searched before it
+    /// without a cutoff, `tried_quiets`, lose history.
+    pub fn record_cutoff(
+        &mut self,
+        color: Color,
+        ply: usize,
+        depth: u8,
+        mov: Move,
+        previous_move: Option<Move>,
+        tried_quiets: &[Move],
+    ) {
+        if let Some(killers) = self.killers.get_mut(ply)
+            && killers[0] != mov
+        {
+            killers[1] = killers[0];
+            killers[0] = mov;
+        }
+
+        let bonus = (depth as i32 * depth as i32).min(HISTORY_MAX);
+        self.update_history(color, mov, bonus);
+        for tried in tried_quiets {
+            self.update_history(color, *tried, -bonus);
+        }
+
+        if let Some(previous_move) = previous_move {
+            self.countermoves[previous_move.from().index() as usize]
+                [previous_move.to().index() as usize] = mov;
+        }
+    }
+
+    fn update_history(&mut self, color: Color, mov: Move, bonus: i32) {
+        let entry = &mut self.history[color as usize][mov.from().index() as usize]
+            [mov.to().index() as usize];
+        // The more extreme the score, the less it moves, so it never leaves the bounds.
+        *entry += bonus - *entry * bonus.abs() / HISTORY_MAX;
+    }
+}
+
+impl Default for MoveHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The legal moves of a position, best first: the hash move, then captures and promotions by
+/// most valuable victim and least valuable attacker, then the killer moves of the ply, the
+/// countermove of the previous move and the other quiet moves by history.
+///
+/// Moves are scored once and picked lazily, a cutoff on the first moves spares sorting the rest.
+pub struct MovePicker {
+    moves: MoveList,
+    scores: [i32; MAX_MOVES],
+    next: usize,
+}
+
+impl MovePicker {
+    pub fn new(
+        board: &ChessBoard,
+        hash_move: Option<Move>,
+        ply: usize,
+        previous_move: Option<Move>,
+        move_history: &MoveHistory,
+    ) -> Self {
+        let moves = all_valid_moves(board);
+        let killers = move_history.killers(ply);
+        let countermove = previous_move.and_then(|mov| move_history.countermove(mov));
+        let color = board.current_turn();
+
+        let mut scores = [0; MAX_MOVES];
+        for (score, mov) in scores.iter_mut().zip(moves.iter()) {
+            *score = if Some(*mov) == hash_move {
+                HASH_MOVE_SCORE
+            } else if !is_quiet(*mov) {
+                CAPTURE_SCORE + mvv_lva(board, *mov)
+            } else if *mov == killers[0] {
+                FIRST_KILLER_SCORE
+            } else if *mov == killers[1] {
+                SECOND_KILLER_SCORE
+            } else if Some(*mov) == countermove {
+                COUNTERMOVE_SCORE
+            } else {
+                move_history.history(color, *mov)
+            };
+        }
+        Self {
+            moves,
+            scores,
+            next: 0,
+        }
+    }
+
+    /// The number of legal moves, picked or not.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+}
+
+impl Iterator for MovePicker {
+    type Item = Move;
+
+    /// Selection sort, one step at a time: swaps the best remaining move to the front.
+    fn next(&mut self) -> Option<Move> {
+        if self.next >= self.moves.len() {
+            return None;
+        }
+        let mut best = self.next;
+        for index in self.next + 1..self.moves.len() {
+            if self.scores[index] > self.scores[best] {
+                best = index;
+            }
+        }
+        self.moves.swap(self.next, best);
+        self.scores.swap(self.next, best);
+        self.next += 1;
+        Some(self.moves[self.next - 1])
+    }
+}
+
+fn piece_order(kind: PieceKind) -> i32 {
+    match kind {
+        PieceKind::Pawn => 1,
+        PieceKind::Knight => 2,
+        PieceKind::Bishop => 3,
+        PieceKind::Rook => 4,
+        PieceKind::Queen => 5,
+        PieceKind::King => 6,
+    }
+}
+
+/// Most valuable victim first, least valuable attacker between equal victims. A queen
+/// promotion counts as capturing a queen, underpromotions come after every capture.
+fn mvv_lva(board: &ChessBoard, mov: Move) -> i32 {
+    let victim = if mov.is_en_passant() {
+        piece_order(PieceKind::Pawn)
+    } else {
+        match board.at(mov.to()) {
+            SquareContent::Occupied(piece) if mov.is_capture() => piece_order(piece.kind),
+            _ => 0,
+        }
+    };
+    let promotion = match mov.promoted_piece_kind() {
+        Some(PieceKind::Queen) => piece_order(PieceKind::Queen),
+        _ => 0,
+    };
+    let attacker = piece_order(board.piece_at_source_or_panic(mov.from()).kind);
+    (victim + promotion) * 8 - attacker
+}
@@ -0,0 +1,229 @@
+use crate::chess_move::{MakeMove, Move};
+use crate::valid_moves::{all_valid_moves, is_check};
+use board_representation::chess_board::ChessBoard;
+use board_representation::chess_piece::{Color, Piece, PieceKind};
+use board_representation::square::{File, Rank, Square};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SanParseError {
+    InvalidSyntax {
+        found: String,
+    },
+    /// The SAN string is well formed but no legal move matches it.
+    IllegalMove {
+        found: String,
+    },
+    /// Several legal moves match the SAN string, it lacks a disambiguation.
+    AmbiguousMove {
+        found: String,
+    },
+}
+
+impl fmt::Display for SanParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SanParseError::InvalidSyntax { found } => {
+                write!(f, "Invalid SAN string '{}'", found)
+            }
+            SanParseError::IllegalMove { found } => {
+                write!(f, "No legal move matches the SAN string '{}'", found)
+            }
+            SanParseError::AmbiguousMove { found } => {
+                write!(f, "Several legal moves match the SAN string '{}'", found)
+            }
+        }
+    }
+}
+
+impl Error for SanParseError {}
+
+fn piece_letter(kind: PieceKind) -> char {
+    Piece {
+        kind,
+        color: Color::White,
+    }
+    .to_fen_char()
+}
+
+/// The parts of a SAN move once the decorations (captures, checks, annotations) are removed.
+struct SanMove {
+    kind: PieceKind,
+    from_file: Option<File>,
+    from_rank: Option<Rank>,
+    to: Square,
+    promoted_piece_kind: Option<PieceKind>,
+}
+
+impl SanMove {
+    fn matches(&self, board: &ChessBoard, mov: Move) -> bool {
+        mov.to() == self.to
+            && board.piece_at_source_or_panic(mov.from()).kind == self.kind
+            && self.from_file.is_none_or(|file| mov.from().file() == file)
+            && self.from_rank.is_none_or(|rank| mov.from().rank() == rank)
+            && mov.promoted_piece_kind() == self.promoted_piece_kind
+    }
+}
+
+fn parse_san_move(san: &str) -> Option<SanMove> {
+    let mut chars = san.chars().collect::<Vec<char>>();
+
+    let promoted_piece_kind = match chars.as_slice() {
+        [.., '=', c]
+        | [
+            ..,
+            '1'..='8',
+            c @ ('Q' | 'R' | 'B' | 'N' | 'q' | 'r' | 'b' | 'n'),
+        ] => {
+            let kind = Piece::from_fen_char(*c)?.kind;
+            if matches!(kind, PieceKind::Pawn | PieceKind::King) {
+                return None;
+            }
+            let length = chars.len() - if chars[chars.len() - 2] == '=' { 2 } else { 1 };
+            chars.truncate(length);
+            Some(kind)
+        }
+        _ => None,
+    };
+
+    let kind = match chars.first() {
+        Some(c @ ('K' | 'Q' | 'R' | 'B' | 'N')) => {
+            let kind = Piece::from_fen_char(*c)?.kind;
+            chars.remove(0);
+            kind
+        }
+        _ => PieceKind::Pawn,
+    };
+
+    let to = match chars.as_slice() {
+        [.., file, rank] => Square::new(File::from_char(*file)?, Rank::from_char(*rank)?),
+        _ => return None,
+    };
+    chars.truncate(chars.len() - 2);
+
+    let (mut from_file, mut from_rank) = (None, None);
+    for c in chars.into_iter().filter(|c| !matches!(c, 'x' | ':' | '-')) {
+        match c {
+            'a'..='h' if from_file.is_none() => from_file = File::from_char(c),
+            '1'..='8' if from_rank.is_none() => from_rank = Rank::from_char(c),
+            _ => return None,
+        }
+    }
+
+    Some(SanMove {
+        kind,
+        from_file,
+        from_rank,
+        to,
+        promoted_piece_kind,
+    })
+}
+
+impl Move {
+    /// Formats this move in Standard Algebraic Notation, e.g. `Nbd7`, `exd6`, `e8=Q+` or `O-O#`.
+    ///
+    /// `board` is the position the move is played in, and the move must be legal in it.
+    pub fn to_san(self, board: &ChessBoard) -> String {
+        let mut san = match self.flags() {
+            Move::KING_CASTLE => "O-O".to_string(),
+            Move::QUEEN_CASTLE => "O-O-O".to_string(),
+            _ => self.piece_san(board),
+        };
+
+        let mut board_after_move = *board;
+        board_after_move.make_move(self);
+        if is_check(&board_after_move) {
+            san.push(if all_valid_moves(&board_after_move).is_empty() {
+                '#'
+            } else {
+                '+'
+            });
+        }
+        san
+    }
+
+    fn piece_san(self, board: &ChessBoard) -> String {
+        let (from, to) = (self.from(), self.to());
+        let kind = board.piece_at_source_or_panic(from).kind;
+        let mut san = String::new();
+
+        if kind == PieceKind::Pawn {
+            if self.is_capture() {
+                san.push(from.file().to_char());
+            }
+        } else {
+            san.push(piece_letter(kind));
+            // Other pieces of the same kind that can reach the same square.
+            let rivals = all_valid_moves(board)
+                .into_iter()
+                .filter(|mov| {
+                    mov.to() == to
+                        && mov.from() != from
+                        && board.piece_at_source_or_panic(mov.from()).kind == kind
+                })
+                .collect::<Vec<_>>();
+            if !rivals.is_empty() {
+                if rivals.iter().all(|mov| mov.from().file() != from.file()) {
+                    san.push(from.file().to_char());
+                } else if rivals.iter().all(|mov| mov.from().rank() != from.rank()) {
+                    san.push(from.rank().to_char());
+                } else {
+                    san.push_str(&from.to_string());
+                }
+            }
+        }
+
+        if self.is_capture() {
+            san.push('x');
+        }
+        san.push_str(&to.to_string());
+        if let Some(promoted_piece_kind) = self.promoted_piece_kind() {
+            san.push('=');
+            san.push(piece_letter(promoted_piece_kind));
+        }
+        san
+    }
+
+    /// Parses a move in Standard Algebraic Notation among the legal moves of `board`.
+    ///
+    /// Check and annotation suffixes are ignored, and common sloppy forms are accepted:
+    /// superfluous disambiguations (`Nbd7`, `e2e4`), missing capture marks, `e.p.` suffixes,
+    /// castling with zeros (`0-0`) and promotions without `=` (`e8Q`).
+    pub fn from_san(board: &ChessBoard, san: &str) -> Result<Move, SanParseError> {
+        let found = || san.to_string();
+        let mut trimmed = san.trim().trim_end_matches(['+', '#', '!', '?']);
+        for suffix in ["e.p.", "ep"] {
+            if let Some(stripped) = trimmed.strip_suffix(suffix) {
+                trimmed = stripped.trim_end();
+            }
+        }
+
+        let legal_moves = all_valid_moves(board);
+        let mut candidates = match trimmed {
+            "O-O" | "0-0" => legal_moves
+                .into_iter()
+                .filter(|mov| mov.flags() == Move::KING_CASTLE)
+                .collect::<Vec<_>>(),
+            "O-O-O" | "0-0-0" => legal_moves
+                .into_iter()
+                .filter(|mov| mov.flags() == Move::QUEEN_CASTLE)
+                .collect(),
+            _ => {
+                let san_move = parse_san_move(trimmed)
+                    .ok_or(SanParseError::InvalidSyntax { found: found() })?;
+                legal_moves
+                    .into_iter()
+                    .filter(|mov| san_move.matches(board, *mov))
+                    .collect()
+            }
+        };
+
+        match candidates.len() {
+            0 => Err(SanParseError::IllegalMove { found: found() }),
+            1 => Ok(candidates.remove(0)),
+            _ => Err(SanParseError::AmbiguousMove { found: found() }),
+        }
+    }
+}
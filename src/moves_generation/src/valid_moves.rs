@@ -1,261 +1,415 @@
 use crate::chess_move::Move;
-use crate::move_directions::*;
-use crate::moves_generation_utils::{
-    apply_delta, apply_delta_with_dist, is_king_checked, is_square_checked,
+use crate::king_safety::KingSafety;
+use crate::move_list::MoveList;
+use crate::moves_generation_utils::{attackers, is_king_checked};
+use board_representation::attacks::{
+    bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks, rook_attacks,
 };
-use board_representation::chess_board::{ChessBoard, Square, SquareIndex};
-use board_representation::chess_piece::{Color, Piece, PieceKind, PROMOTABLE_KINDS};
+use board_representation::bitboard::Bitboard;
+use board_representation::chess_board::{CastlingSide, ChessBoard};
+use board_representation::chess_piece::{Color, PROMOTABLE_KINDS, PieceKind};
+use board_representation::square::{Rank, Square};
+
+/// Generates the moves of the piece on `square` landing on `targets`, the squares it can reach
+/// without leaving its king in check.
+type PieceMovesGenerator = fn(&ChessBoard, Square, &Color, Bitboard, &mut MoveList);
 
 // TODO: use builder for valid moves creation
-fn slider_valid_moves(
-    board: &ChessBoard,
-    index: SquareIndex,
-    color: &Color,
-    directions: &[SquareIndexDelta],
-) -> Vec<Move> {
-    let mut moves: Vec<Move> = Vec::new();
-    for delta in directions {
-        let mut dist = 1;
-
-        loop {
-            let next_position = apply_delta_with_dist(index, *delta, dist);
-            match board.within_bounds_and_empty(next_position) {
-                Some(to_index) => {
-                    moves.push(Move::base_move(index, to_index));
-                    dist += 1
-                }
-                None => {
-                    match board.within_bounds_and_occupied_by_opponent(next_position, color) {
-                        None => {}
-                        Some(to_index) => moves.push(Move::base_move(index, to_index)),
-                    }
-                    break;
-                }
-            }
-        }
+/// `targets` never contains pieces of the side to move, so occupied targets are captures.
+fn moves_to_targets(board: &ChessBoard, from: Square, targets: Bitboard, moves: &mut MoveList) {
+    let occupancy = board.occupancy();
+    for to in targets & occupancy {
+        moves.push(Move::capture(from, to));
     }
-    moves
-}
-
-fn leaper_valid_moves(
-    board: &ChessBoard,
-    index: SquareIndex,
-    color: &Color,
-    directions: &[SquareIndexDelta],
-) -> Vec<Move> {
-    let mut moves = Vec::new();
-
-    for delta in directions {
-        let to_index = apply_delta(index, *delta);
-        if let Some(to_index) = board.within_bounds_and_empty(to_index) {
-            moves.push(Move::base_move(index, to_index));
-        } else if let Some(to_index) = board.within_bounds_and_occupied_by_opponent(to_index, color)
-        {
-            moves.push(Move::base_move(index, to_index));
-        }
+    for to in targets & !occupancy {
+        moves.push(Move::quiet(from, to));
     }
-    moves
 }
 
-fn knight_valid_moves(board: &ChessBoard, index: SquareIndex, color: &Color) -> Vec<Move> {
-    leaper_valid_moves(board, index, color, &KNIGHT_DIRECTIONS)
+fn knight_valid_moves(
+    board: &ChessBoard,
+    square: Square,
+    _color: &Color,
+    targets: Bitboard,
+    moves: &mut MoveList,
+) {
+    moves_to_targets(board, square, knight_attacks(square) & targets, moves)
 }
 
-fn king_valid_moves(board: &ChessBoard, index: SquareIndex, color: &Color) -> Vec<Move> {
-    leaper_valid_moves(board, index, color, &KING_DIRECTIONS)
+fn rook_valid_moves(
+    board: &ChessBoard,
+    square: Square,
+    _color: &Color,
+    targets: Bitboard,
+    moves: &mut MoveList,
+) {
+    let attacks = rook_attacks(square, board.occupancy());
+    moves_to_targets(board, square, attacks & targets, moves)
 }
 
-fn rook_valid_moves(board: &ChessBoard, index: SquareIndex, color: &Color) -> Vec<Move> {
-    slider_valid_moves(board, index, color, &ROOK_DIRECTIONS)
+fn bishop_valid_moves(
+    board: &ChessBoard,
+    square: Square,
+    _color: &Color,
+    targets: Bitboard,
+    moves: &mut MoveList,
+) {
+    let attacks = bishop_attacks(square, board.occupancy());
+    moves_to_targets(board, square, attacks & targets, moves)
 }
 
-fn bishop_valid_moves(board: &ChessBoard, index: SquareIndex, color: &Color) -> Vec<Move> {
-    slider_valid_moves(board, index, color, &BISHOP_DIRECTIONS)
+fn queen_valid_moves(
+    board: &ChessBoard,
+    square: Square,
+    _color: &Color,
+    targets: Bitboard,
+    moves: &mut MoveList,
+) {
+    let attacks = queen_attacks(square, board.occupancy());
+    moves_to_targets(board, square, attacks & targets, moves)
 }
 
-fn queen_valid_moves(board: &ChessBoard, index: SquareIndex, color: &Color) -> Vec<Move> {
-    slider_valid_moves(board, index, color, &KING_DIRECTIONS)
+/// The king can go to any square not attacked by the opponent. Sliders see through the king,
+/// otherwise stepping back along the line of a check would look safe.
+fn king_valid_moves(
+    board: &ChessBoard,
+    king: Square,
+    color: &Color,
+    targets: Bitboard,
+    moves: &mut MoveList,
+) {
+    let occupancy_without_king = board.occupancy() & !king.bitboard();
+    let mut safe_targets = Bitboard::EMPTY;
+    for to in king_attacks(king) & targets {
+        if attackers(board, to, !*color, occupancy_without_king).is_empty() {
+            safe_targets |= to.bitboard();
+        }
+    }
+    moves_to_targets(board, king, safe_targets, moves);
 }
 
-fn is_promotion_row(index: SquareIndex, color: &Color) -> bool {
-    // todo: it is a bit scary that this is not covered properly
-    match color {
-        Color::White => index > 55,
-        Color::Black => index < 8,
-    }
+fn is_promotion_row(square: Square, color: &Color) -> bool {
+    square.rank() == Rank::Eighth.relative_to(*color)
 }
 
-fn maybe_promotion_moves(from: SquareIndex, to: SquareIndex, color: &Color) -> Vec<Move> {
-    let mut moves = Vec::new();
-    if !is_promotion_row(to, color) {
-        moves.push(Move::base_move(from, to));
-    } else {
-        PROMOTABLE_KINDS.into_iter().for_each(|promoted_piece| {
-            moves.push(Move {
-                from,
-                to,
-                promoted_piece_kind: Some(promoted_piece),
-            })
-        })
+fn push_maybe_promotion_moves(
+    from: Square,
+    to: Square,
+    color: &Color,
+    is_capture: bool,
+    moves: &mut MoveList,
+) {
+    match (is_promotion_row(to, color), is_capture) {
+        (false, false) => moves.push(Move::quiet(from, to)),
+        (false, true) => moves.push(Move::capture(from, to)),
+        (true, false) => PROMOTABLE_KINDS
+            .into_iter()
+            .for_each(|promoted_piece| moves.push(Move::promotion(from, to, promoted_piece))),
+        (true, true) => PROMOTABLE_KINDS.into_iter().for_each(|promoted_piece| {
+            moves.push(Move::promotion_capture(from, to, promoted_piece))
+        }),
     }
-    moves
 }
 
-fn is_initial_pawn_raw(index: SquareIndex, color: &Color) -> bool {
-    match color {
-        Color::White => index >= 8 && index < 16,
-        Color::Black => index >= 48 && index < 56,
-    }
+fn is_initial_pawn_raw(square: Square, color: &Color) -> bool {
+    square.rank() == Rank::Second.relative_to(*color)
 }
 
-fn pawn_valid_moves(board: &ChessBoard, index: SquareIndex, color: &Color) -> Vec<Move> {
-    let mut moves = Vec::new();
-    let dir = match color {
-        Color::White => 1,
-        Color::Black => -1,
-    };
-    let to_index_single = apply_delta(index, (dir, 0));
-    if let Some(to_index_single) = board.within_bounds_and_empty(to_index_single) {
-        moves.extend(maybe_promotion_moves(index, to_index_single, color));
-        if is_initial_pawn_raw(index, color) {
-            let to_index_double = apply_delta(index, (dir * 2, 0));
-            if let Some(to_index_double) = board.within_bounds_and_empty(to_index_double) {
-                moves.push(Move::base_move(index, to_index_double));
-            }
+/// Pushes to the last rank count as captures for `stage`, like every other promotion.
+fn pawn_valid_moves(
+    board: &ChessBoard,
+    square: Square,
+    color: &Color,
+    targets: Bitboard,
+    stage: Stage,
+    moves: &mut MoveList,
+) {
+    let empty_squares = !board.occupancy();
+    // Pawns on the last rank only show up in hand-made positions, and cannot be pushed.
+    if let Some(single_push) = square.forward(*color)
+        && empty_squares.contains(single_push)
+    {
+        let is_promotion = is_promotion_row(single_push, color);
+        let is_in_stage = if is_promotion {
+            stage.captures
+        } else {
+            stage.quiets
+        };
+        if is_in_stage && targets.contains(single_push) {
+            push_maybe_promotion_moves(square, single_push, color, false, moves);
+        }
+        if stage.quiets
+            && is_initial_pawn_raw(square, color)
+            && let Some(double_push) = single_push.forward(*color)
+            && (empty_squares & targets).contains(double_push)
+        {
+            moves.push(Move::new(square, double_push, Move::DOUBLE_PAWN_PUSH));
         }
     }
 
-    let to_index_take_right = apply_delta(index, (dir, 1));
-    if let Some(to_index_take_right) =
-        board.within_bounds_and_pawn_take_target(to_index_take_right, color)
-    {
-        moves.extend(maybe_promotion_moves(index, to_index_take_right, color));
+    if !stage.captures {
+        return;
+    }
+    let captures = pawn_attacks(square, *color) & board.pieces_of(!*color) & targets;
+    for to in captures {
+        push_maybe_promotion_moves(square, to, color, true, moves);
     }
-    let to_index_take_left = apply_delta(index, (dir, -1));
-    if let Some(to_index_take_left) =
-        board.within_bounds_and_pawn_take_target(to_index_take_left, color)
+
+    if let Some(en_passant_target) = board.en_passant_target_square()
+        && pawn_attacks(square, *color).contains(en_passant_target)
     {
-        moves.extend(maybe_promotion_moves(index, to_index_take_left, color));
+        let mov = Move::new(square, en_passant_target, Move::EN_PASSANT);
+        if is_en_passant_legal(board, mov, color) {
+            moves.push(mov);
+        }
     }
-    moves
 }
 
-fn is_kingside_castle_possible(board: &ChessBoard, color: &Color) -> bool {
-    if *color == Color::White && !board.can_white_castle_kingside() {
+/// En passant removes two pieces from the same rank at once, so pin masks are not enough:
+/// the capture is played on the occupancy and the king is checked for attackers.
+fn is_en_passant_legal(board: &ChessBoard, mov: Move, color: &Color) -> bool {
+    let captured = mov.en_passant_capture_square().bitboard();
+    let occupancy = (board.occupancy() & !mov.from().bitboard() & !captured) | mov.to().bitboard();
+    let king = board.find_king(*color);
+    (attackers(board, king, !*color, occupancy) & !captured).is_empty()
+}
+
+/// The squares of the rank of `a` and `b` from one to the other, both included.
+fn rank_span(a: Square, b: Square) -> Bitboard {
+    let (low, high) = (a.index().min(b.index()), a.index().max(b.index()));
+    Bitboard(!0 << low & !0 >> (63 - high))
+}
+
+/// Castling follows the same rules in standard chess and Chess960: the squares the king and
+/// the rook cross or land on are empty but for the two of them, and the king does not start
+/// on, cross or land on an attacked square.
+fn is_castle_possible(board: &ChessBoard, king: Square, color: &Color, side: CastlingSide) -> bool {
+    if !board.can_castle(*color, side) {
+        return false;
+    }
+    let rook = board.castling_rook_square(*color, side);
+    if !(board.rooks & board.pieces_of(*color)).contains(rook) {
         return false;
     }
 
-    if *color == Color::Black && !board.can_black_castle_kingside() {
+    let king_path = rank_span(king, Square::new(side.king_destination(), king.rank()));
+    let rook_path = rank_span(rook, Square::new(side.rook_destination(), king.rank()));
+    let occupancy = board.occupancy() & !rook.bitboard();
+    if !(occupancy & !king.bitboard() & (king_path | rook_path)).is_empty() {
         return false;
     }
+    // Without the rook, which may be shielding the king from a slider on the back rank.
+    king_path
+        .into_iter()
+        .all(|square| attackers(board, square, !*color, occupancy).is_empty())
+}
 
-    let empty_square_indexes = match color {
-        Color::White => [5, 6],
-        Color::Black => [61, 62],
-    };
-    for index in empty_square_indexes {
-        if board.at(index) != Square::Empty {
-            return false;
-        }
+/// Moves of every piece but the king, restricted by the checks and pins in `king_safety`.
+fn pieces_valid_moves(
+    board: &ChessBoard,
+    king_safety: &KingSafety,
+    stage: Stage,
+    moves: &mut MoveList,
+) {
+    let color = board.current_turn();
+    let own_pieces = board.pieces_of(color);
+    for square in board.pawns & own_pieces {
+        let targets = king_safety.legal_targets(square) & !own_pieces;
+        pawn_valid_moves(board, square, &color, targets, stage, moves);
     }
 
-    let non_checked_square_indexes = match color {
-        Color::White => [4, 5, 6],
-        Color::Black => [60, 61, 62],
-    };
-    for index in non_checked_square_indexes {
-        if is_square_checked(board, index, *color) {
-            return false;
+    let generators: [(Bitboard, PieceMovesGenerator); 4] = [
+        (board.knights, knight_valid_moves),
+        (board.bishops, bishop_valid_moves),
+        (board.rooks, rook_valid_moves),
+        (board.queens, queen_valid_moves),
+    ];
+    let stage_targets = stage.targets(board);
+    for (pieces, generator) in generators {
+        for square in pieces & own_pieces {
+            let targets = king_safety.legal_targets(square) & stage_targets;
+            generator(board, square, &color, targets, moves);
         }
     }
-
-    true
 }
 
-fn is_queenside_castle_possible(board: &ChessBoard, color: &Color) -> bool {
-    if *color == Color::White && !board.can_white_castle_queenside() {
-        return false;
+/// Moves out of check: the king steps away, or in single check the checker is captured or
+/// the check is blocked. Castling is never allowed.
+fn evasions(board: &ChessBoard, king_safety: &KingSafety, stage: Stage, moves: &mut MoveList) {
+    if !king_safety.is_in_double_check() {
+        pieces_valid_moves(board, king_safety, stage, moves);
     }
+    let color = board.current_turn();
+    king_valid_moves(board, king_safety.king, &color, stage.targets(board), moves);
+}
 
-    if *color == Color::Black && !board.can_black_castle_queenside() {
-        return false;
+fn non_evasions(board: &ChessBoard, king_safety: &KingSafety, stage: Stage, moves: &mut MoveList) {
+    pieces_valid_moves(board, king_safety, stage, moves);
+    let color = board.current_turn();
+    king_valid_moves(board, king_safety.king, &color, stage.targets(board), moves);
+    if !stage.quiets {
+        return;
     }
-
-    let empty_square_indexes = match color {
-        Color::White => [1, 2, 3],
-        Color::Black => [57, 58, 59],
-    };
-    for index in empty_square_indexes {
-        if board.at(index) != Square::Empty {
-            return false;
+    for side in CastlingSide::ALL {
+        if is_castle_possible(board, king_safety.king, &color, side) {
+            let rook = board.castling_rook_square(color, side);
+            moves.push(Move::castle(king_safety.king, rook, side));
         }
     }
+}
 
-    let non_checked_square_indexes = match color {
-        Color::White => [2, 3, 4],
-        Color::Black => [58, 59, 60],
+/// Which kinds of legal moves a generation pass produces.
+#[derive(Clone, Copy)]
+struct Stage {
+    /// Captures, en passant included, and every promotion.
+    captures: bool,
+    /// All the other moves, castling included.
+    quiets: bool,
+}
+
+impl Stage {
+    const ALL: Stage = Stage {
+        captures: true,
+        quiets: true,
     };
-    for index in non_checked_square_indexes {
-        if is_square_checked(board, index, *color) {
-            return false;
+
+    /// Squares the pieces but pawns may move to in this stage.
+    fn targets(&self, board: &ChessBoard) -> Bitboard {
+        let opponent_pieces = board.pieces_of(!board.current_turn());
+        let empty_squares = !board.occupancy();
+        match (self.captures, self.quiets) {
+            (true, true) => opponent_pieces | empty_squares,
+            (true, false) => opponent_pieces,
+            (false, true) => empty_squares,
+            (false, false) => Bitboard::EMPTY,
         }
     }
-
-    true
 }
 
-fn piece_valid_moves(board: &ChessBoard, index: SquareIndex, piece: &Piece) -> Vec<Move> {
-    match piece.kind {
-        PieceKind::Pawn => pawn_valid_moves(board, index, &piece.color),
-        PieceKind::Rook => rook_valid_moves(board, index, &piece.color),
-        PieceKind::Bishop => bishop_valid_moves(board, index, &piece.color),
-        PieceKind::Queen => queen_valid_moves(board, index, &piece.color),
-        PieceKind::Knight => knight_valid_moves(board, index, &piece.color),
-        PieceKind::King => king_valid_moves(board, index, &piece.color),
+fn generate_stage(board: &ChessBoard, stage: Stage) -> MoveList {
+    let king_safety = KingSafety::new(board);
+    let mut moves = MoveList::new();
+    if king_safety.is_in_check() {
+        evasions(board, &king_safety, stage, &mut moves);
+    } else {
+        non_evasions(board, &king_safety, stage, &mut moves);
     }
+    moves
 }
 
-fn all_possible_moves(board: &ChessBoard) -> Vec<Move> {
-    let mut moves = Vec::new();
-    let color = board.current_turn();
-    board.for_each_piece(|index, piece| {
-        if piece.color == color {
-            let valid_moves = piece_valid_moves(board, index, piece);
-            moves.extend(valid_moves);
-        }
-    });
-    moves
+/// All the legal moves of the side to move. Checks and pins are computed up front,
+/// so no move has to be played to find out whether it leaves the king in check.
+pub fn all_valid_moves(board: &ChessBoard) -> MoveList {
+    generate_stage(board, Stage::ALL)
+}
+
+/// Selects the moves produced by [`generate`]. Whatever the mode, only legal moves are
+/// generated, and `Captures` and `Quiets` together give exactly [`all_valid_moves`].
+pub trait GenerationMode {
+    #[doc(hidden)]
+    const CAPTURES: bool;
+    #[doc(hidden)]
+    const QUIETS: bool;
+    /// Keeps only the moves giving check.
+    #[doc(hidden)]
+    const ONLY_CHECKS: bool = false;
+    /// Generates nothing when the side to move is not in check.
+    #[doc(hidden)]
+    const ONLY_IN_CHECK: bool = false;
+}
+
+/// Captures, en passant included, and every promotion, quiet ones included.
+pub struct Captures;
+
+/// Every move that is not in [`Captures`], castling included.
+pub struct Quiets;
+
+/// All the legal moves when in check, nothing otherwise.
+pub struct Evasions;
+
+/// The moves of [`Quiets`] that give check.
+pub struct QuietChecks;
+
+impl GenerationMode for Captures {
+    const CAPTURES: bool = true;
+    const QUIETS: bool = false;
+}
+
+impl GenerationMode for Quiets {
+    const CAPTURES: bool = false;
+    const QUIETS: bool = true;
+}
+
+impl GenerationMode for Evasions {
+    const CAPTURES: bool = true;
+    const QUIETS: bool = true;
+    const ONLY_IN_CHECK: bool = true;
 }
 
-fn filter_king_going_under_check(board: &ChessBoard, moves: Vec<Move>) -> Vec<Move> {
+impl GenerationMode for QuietChecks {
+    const CAPTURES: bool = false;
+    const QUIETS: bool = true;
+    const ONLY_CHECKS: bool = true;
+}
+
+/// Generates the legal moves selected by `M`, for example `generate::<Captures>(&board)`.
+pub fn generate<M: GenerationMode>(board: &ChessBoard) -> MoveList {
+    if M::ONLY_IN_CHECK && !is_check(board) {
+        return MoveList::new();
+    }
+    let stage = Stage {
+        captures: M::CAPTURES,
+        quiets: M::QUIETS,
+    };
+    let mut moves = generate_stage(board, stage);
+    if M::ONLY_CHECKS {
+        let mut index = 0;
+        while index < moves.len() {
+            if gives_check(board, moves[index]) {
+                index += 1;
+            } else {
+                moves.swap_remove(index);
+            }
+        }
+    }
     moves
-        .iter()
-        .filter(|mov| {
-            let piece = board.piece_at_source_or_panic(mov.from);
-            let mut board_after_move = board.clone();
-            mov.move_piece(&mut board_after_move);
-            !is_king_checked(&board_after_move, piece.color)
-        })
-        .copied()
-        .collect()
 }
 
-pub fn all_valid_moves(board: &ChessBoard) -> Vec<Move> {
-    let mut moves = filter_king_going_under_check(board, all_possible_moves(board));
+/// Whether the legal move `mov` puts the opponent king in check, directly or by uncovering
+/// a slider, without playing it.
+pub fn gives_check(board: &ChessBoard, mov: Move) -> bool {
+    let (from, to) = (mov.from(), mov.destination());
     let color = board.current_turn();
-    if is_kingside_castle_possible(board, &color) {
-        let (from, to) = match color {
-            Color::White => (4, 6),
-            Color::Black => (60, 62),
-        };
-        moves.push(Move::base_move(from, to))
+    let opponent_king = board.find_king(!color);
+    let own_pieces = board.pieces_of(color) & !from.bitboard();
+    let mut occupancy = board.occupancy() & !from.bitboard();
+    let mut rooks_and_queens = (board.rooks | board.queens) & own_pieces;
+    let bishops_and_queens = (board.bishops | board.queens) & own_pieces;
+
+    if mov.is_en_passant() {
+        occupancy &= !mov.en_passant_capture_square().bitboard();
     }
-    if is_queenside_castle_possible(board, &color) {
-        let (from, to) = match color {
-            Color::White => (4, 2),
-            Color::Black => (60, 58),
-        };
-        moves.push(Move::base_move(from, to))
+    // The rook moves too, and may give check itself.
+    if let Some((rook_from, rook_to)) = mov.castling_rook_squares() {
+        occupancy = occupancy & !rook_from.bitboard() | rook_to.bitboard();
+        rooks_and_queens = rooks_and_queens & !rook_from.bitboard() | rook_to.bitboard();
     }
-    moves
+    occupancy |= to.bitboard();
+
+    let moving_kind = board.piece_at_source_or_panic(from).kind;
+    let direct_attacks = match mov.promoted_piece_kind().unwrap_or(moving_kind) {
+        PieceKind::Pawn => pawn_attacks(to, color),
+        PieceKind::Knight => knight_attacks(to),
+        PieceKind::Bishop => bishop_attacks(to, occupancy),
+        PieceKind::Rook => rook_attacks(to, occupancy),
+        PieceKind::Queen => queen_attacks(to, occupancy),
+        PieceKind::King => Bitboard::EMPTY,
+    };
+    direct_attacks.contains(opponent_king)
+        || !(rook_attacks(opponent_king, occupancy) & rooks_and_queens).is_empty()
+        || !(bishop_attacks(opponent_king, occupancy) & bishops_and_queens).is_empty()
+}
+
+pub fn is_check(board: &ChessBoard) -> bool {
+    is_king_checked(board, board.current_turn())
 }
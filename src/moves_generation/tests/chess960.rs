@@ -0,0 +1,161 @@
+use board_representation::chess_board::ChessBoard;
+use moves_generation::chess_move::{MakeMove, Move};
+use moves_generation::valid_moves::all_valid_moves;
+use std::str::FromStr;
+
+fn perft(depth: u8, chess_board: &mut ChessBoard) -> u64 {
+    let moves = all_valid_moves(chess_board);
+    if depth == 1 {
+        moves.len() as u64
+    } else {
+        let mut res = 0;
+        for mov in moves {
+            let undo_info = chess_board.make_move(mov);
+            res += perft(depth - 1, chess_board);
+            chess_board.unmake_move(mov, undo_info);
+        }
+        res
+    }
+}
+
+/// The first positions of the standard Chess960 perft suite, with the node counts from depth
+/// 1 to 6.
+const PERFT_POSITIONS: [(&str, [u64; 6]); 10] = [
+    (
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        [21, 528, 12189, 326672, 8146062, 227689589],
+    ),
+    (
+        "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        [21, 807, 18002, 667366, 16253601, 590751109],
+    ),
+    (
+        "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+        [20, 479, 10471, 273318, 6417013, 177654692],
+    ),
+    (
+        "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+        [22, 593, 13440, 382958, 9183776, 274103539],
+    ),
+    (
+        "1nbbnrkr/p1p1ppp1/3p4/1p3P1p/3Pq2P/8/PPP1P1P1/QNBBNRKR w HFhf - 0 9",
+        [28, 1120, 31058, 1171749, 34030312, 1250970898],
+    ),
+    (
+        "qnbnr1kr/ppp1b1pp/4p3/3p1p2/8/2NPP3/PPP1BPPP/QNB1R1KR w HEhe - 1 9",
+        [29, 899, 26578, 824055, 24851983, 775718317],
+    ),
+    (
+        "q1bnrkr1/ppppp2p/2n2p2/4b1p1/2NP4/8/PPP1PPPP/QNB1RRKB w ge - 1 9",
+        [30, 860, 24566, 732757, 21093346, 649209803],
+    ),
+    (
+        "qbn1brkr/ppp1p1p1/2n4p/3p1p2/P7/6PP/QPPPPP2/1BNNBRKR w HFhf - 0 9",
+        [25, 635, 17054, 465806, 13203304, 377184252],
+    ),
+    (
+        "qnnbbrkr/1p2ppp1/2pp3p/p7/1P5P/2NP4/P1P1PPP1/Q1NBBRKR w HFhf - 0 9",
+        [24, 572, 15243, 384260, 11110203, 293989890],
+    ),
+    (
+        "qn1rbbkr/ppp2p1p/1n1pp1p1/8/3P4/P6P/1PP1PPPK/QNNRBB1R w hd - 2 9",
+        [28, 811, 23175, 679699, 19836606, 594527992],
+    ),
+];
+
+fn castles(board: &ChessBoard) -> Vec<Move> {
+    all_valid_moves(board)
+        .into_iter()
+        .filter(|mov| mov.is_castling())
+        .collect()
+}
+
+fn fen_after(board: &ChessBoard, mov: Move) -> String {
+    let mut board_after_move = *board;
+    let undo_info = board_after_move.make_move(mov);
+    let fen = board_after_move.to_fen();
+    board_after_move.unmake_move(mov, undo_info);
+    assert_eq!(board_after_move, *board);
+    fen
+}
+
+fn assert_perft(max_depth: u8) {
+    for (fen, nodes) in PERFT_POSITIONS {
+        let mut board = ChessBoard::from_str(fen).unwrap();
+        for depth in 1..=max_depth {
+            assert_eq!(
+                perft(depth, &mut board),
+                nodes[depth as usize - 1],
+                "{} at depth {}",
+                fen,
+                depth
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod chess960 {
+    use super::*;
+
+    #[test]
+    fn castling_notation() {
+        let board = ChessBoard::from_str("4k3/8/8/8/8/8/8/1R3KR1 w GB - 0 1").unwrap();
+        let castles = castles(&board);
+        let uci = castles
+            .iter()
+            .map(|mov| (mov.to_uci_string(), mov.to_chess960_uci_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            uci,
+            [
+                ("f1g1".to_string(), "f1g1".to_string()),
+                ("f1c1".to_string(), "f1b1".to_string())
+            ]
+        );
+        for (standard, chess960) in uci {
+            let mov = Move::from_uci_string(&standard, &board).unwrap();
+            assert_eq!(Move::from_uci_string(&chess960, &board), Ok(mov));
+            assert!(castles.contains(&mov));
+        }
+        assert_eq!(
+            fen_after(&board, castles[0]),
+            "4k3/8/8/8/8/8/8/1R3RK1 b - - 1 1"
+        );
+        assert_eq!(
+            fen_after(&board, castles[1]),
+            "4k3/8/8/8/8/8/8/2KR2R1 b - - 1 1"
+        );
+    }
+
+    #[test]
+    fn king_castling_in_place() {
+        let board = ChessBoard::from_str("4k3/8/8/8/8/8/8/6KR w K - 0 1").unwrap();
+        let castles = castles(&board);
+        assert_eq!(castles.len(), 1);
+        assert_eq!(castles[0].to_chess960_uci_string(), "g1h1");
+        assert_eq!(castles[0].to_san(&board), "O-O");
+        assert_eq!(
+            fen_after(&board, castles[0]),
+            "4k3/8/8/8/8/8/8/5RK1 b - - 1 1"
+        );
+    }
+
+    #[test]
+    fn castling_rook_shielding_the_king() {
+        // Once the rook leaves b1, the black rook attacks c1.
+        let board = ChessBoard::from_str("4k3/8/8/8/8/8/8/rR1K4 w B - 0 1").unwrap();
+        assert!(castles(&board).is_empty());
+    }
+
+    #[test]
+    fn perft_positions() {
+        assert_perft(4);
+    }
+
+    #[test]
+    #[ignore = "Slow test"]
+    fn perft_positions_depth_5() {
+        assert_perft(5);
+    }
+}
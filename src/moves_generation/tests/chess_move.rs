@@ -0,0 +1,211 @@
+mod common;
+
+use board_representation::chess_board::ChessBoard;
+use board_representation::chess_piece::PieceKind;
+use board_representation::square::Square;
+use common::for_each_node;
+use moves_generation::Move;
+use moves_generation::chess_move::{MoveParseError, UciMove, move_piece_uci};
+use moves_generation::valid_moves::all_valid_moves;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod chess_move {
+    use super::*;
+
+    #[test]
+    fn parse_uci_string() {
+        let board = ChessBoard::default();
+        assert_eq!(
+            Move::from_uci_string("g1f3", &board),
+            Ok(Move::quiet(Square::G1, Square::F3))
+        );
+        assert_eq!(
+            Move::from_uci_string("e2e4", &board),
+            Ok(Move::new(Square::E2, Square::E4, Move::DOUBLE_PAWN_PUSH))
+        );
+        let board = ChessBoard::from_str("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            Move::from_uci_string("a7a8q", &board),
+            Ok(Move::promotion(Square::A7, Square::A8, PieceKind::Queen))
+        );
+        assert_eq!(
+            Move::from_uci_string("a7b8n", &board),
+            Ok(Move::promotion_capture(
+                Square::A7,
+                Square::B8,
+                PieceKind::Knight
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_special_moves() {
+        let board = ChessBoard::from_str("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap();
+        let parse = |uci| Move::from_uci_string(uci, &board).unwrap();
+        assert!(parse("e5d6").is_en_passant());
+        assert!(parse("e5d6").is_capture());
+        assert_eq!(parse("e1g1").flags(), Move::KING_CASTLE);
+        assert_eq!(parse("e1c1").flags(), Move::QUEEN_CASTLE);
+        assert!(parse("a1a8").is_capture());
+        assert_eq!(parse("e5e6").flags(), Move::QUIET);
+        assert_eq!(parse("e1f1").flags(), Move::QUIET);
+    }
+
+    #[test]
+    fn parse_invalid_uci_string() {
+        let board = ChessBoard::default();
+        let parse = |uci| Move::from_uci_string(uci, &board);
+        assert_eq!(parse(""), Err(MoveParseError::WrongLength { found: 0 }));
+        assert_eq!(
+            parse("e2e4qq"),
+            Err(MoveParseError::WrongLength { found: 6 })
+        );
+        assert_eq!(
+            parse("e9e4"),
+            Err(MoveParseError::InvalidSquare {
+                found: "e9".to_string()
+            })
+        );
+        assert_eq!(
+            parse("e2E4"),
+            Err(MoveParseError::InvalidSquare {
+                found: "E4".to_string()
+            })
+        );
+        assert_eq!(
+            parse("e7e8k"),
+            Err(MoveParseError::InvalidPromotion { found: 'k' })
+        );
+        assert_eq!(
+            parse("é2e4"),
+            Err(MoveParseError::InvalidSquare {
+                found: "é2".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn parse_uci_move_without_board() {
+        assert_eq!(
+            UciMove::try_from("e7e8q"),
+            Ok(UciMove {
+                from: Square::E7,
+                to: Square::E8,
+                promotion: Some(PieceKind::Queen),
+            })
+        );
+        assert_eq!(
+            UciMove::try_from("e2e4qq"),
+            Err(MoveParseError::WrongLength { found: 6 })
+        );
+        let uci_move = UciMove::try_from("e1h1").unwrap();
+        assert_eq!(uci_move.to_string(), "e1h1");
+
+        // The same string is a castle or a rook move depending on the board.
+        let board = ChessBoard::from_str("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert!(uci_move.resolve(&board).is_castling());
+        let board = ChessBoard::from_str("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1").unwrap();
+        assert_eq!(
+            uci_move.resolve(&board),
+            Move::quiet(Square::E1, Square::H1)
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Move::quiet(Square::E2, Square::E4).to_string(), "e2e4");
+        assert_eq!(
+            Move::promotion(Square::E7, Square::E8, PieceKind::Rook).to_string(),
+            "e7e8r"
+        );
+        assert_eq!(
+            Move::promotion_capture(Square::E7, Square::F8, PieceKind::Bishop).to_string(),
+            "e7f8b"
+        );
+    }
+
+    #[test]
+    fn fits_in_two_bytes() {
+        assert_eq!(size_of::<Move>(), 2);
+        assert_eq!(size_of::<Option<Move>>(), 4);
+    }
+
+    #[test]
+    fn bits_round_trip() {
+        let moves = [
+            Move::NULL,
+            Move::quiet(Square::A1, Square::H8),
+            Move::new(Square::H8, Square::A1, Move::KING_CASTLE),
+            Move::promotion_capture(Square::B2, Square::A1, PieceKind::Queen),
+        ];
+        for mov in moves {
+            assert_eq!(Move::from_bits(mov.to_bits()), mov);
+        }
+        assert_ne!(
+            Move::quiet(Square::E2, Square::E4),
+            Move::capture(Square::E2, Square::E4)
+        );
+    }
+
+    #[test]
+    fn flags() {
+        let mov = Move::promotion_capture(Square::B2, Square::A1, PieceKind::Knight);
+        assert!(mov.is_capture() && mov.is_promotion());
+        assert!(!mov.is_en_passant() && !mov.is_castling());
+        assert_eq!(mov.promoted_piece_kind(), Some(PieceKind::Knight));
+        for kind in [
+            PieceKind::Knight,
+            PieceKind::Bishop,
+            PieceKind::Rook,
+            PieceKind::Queen,
+        ] {
+            assert_eq!(
+                Move::promotion(Square::E7, Square::E8, kind).promoted_piece_kind(),
+                Some(kind)
+            );
+        }
+        let mov = Move::new(Square::E5, Square::D6, Move::EN_PASSANT);
+        assert!(mov.is_en_passant() && mov.is_capture() && !mov.is_promotion());
+        assert!(Move::new(Square::E1, Square::C1, Move::QUEEN_CASTLE).is_castling());
+        assert!(Move::new(Square::E2, Square::E4, Move::DOUBLE_PAWN_PUSH).is_double_pawn_push());
+        assert_eq!(
+            Move::quiet(Square::E2, Square::E3).promoted_piece_kind(),
+            None
+        );
+    }
+
+    #[test]
+    fn uci_round_trip_along_perft_trees() {
+        // Parsing the UCI string of every generated move gives back the move, flags included.
+        for_each_node(2, |board| {
+            for mov in all_valid_moves(board) {
+                assert_eq!(
+                    Move::from_uci_string(&mov.to_string(), board),
+                    Ok(mov),
+                    "{}",
+                    board.to_fen()
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn moves_can_be_hashed() {
+        let moves = all_valid_moves(&ChessBoard::default())
+            .into_iter()
+            .chain(all_valid_moves(&ChessBoard::default()))
+            .collect::<HashSet<Move>>();
+        assert_eq!(moves.len(), 20);
+    }
+
+    #[test]
+    fn move_piece_uci_rejects_invalid_strings() {
+        let mut board = ChessBoard::default();
+        assert!(move_piece_uci(&mut board, "e2e").is_err());
+        assert_eq!(board, ChessBoard::default());
+        assert!(move_piece_uci(&mut board, "e2e4").is_ok());
+        assert_ne!(board, ChessBoard::default());
+    }
+}
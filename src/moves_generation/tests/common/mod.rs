@@ -0,0 +1,39 @@
+// Each test file uses its own part of this module.
+#![allow(dead_code)]
+
+use board_representation::chess_board::ChessBoard;
+use moves_generation::chess_move::MakeMove;
+use moves_generation::valid_moves::all_valid_moves;
+use std::str::FromStr;
+
+/// The start position and the five positions of the Chess Programming Wiki perft suite,
+/// between them every kind of move: castling, en passant, promotions, checks and pins.
+pub const PERFT_POSITIONS: [&str; 6] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+];
+
+/// Calls `check` on every node of the perft trees of `PERFT_POSITIONS`, down to `depth`
+/// moves from the roots. The moves are played and taken back on a single board, which
+/// `check` must leave as it found it.
+pub fn for_each_node(depth: u8, mut check: impl FnMut(&mut ChessBoard)) {
+    for fen in PERFT_POSITIONS {
+        walk(depth, &mut ChessBoard::from_str(fen).unwrap(), &mut check);
+    }
+}
+
+fn walk(depth: u8, board: &mut ChessBoard, check: &mut impl FnMut(&mut ChessBoard)) {
+    check(board);
+    if depth == 0 {
+        return;
+    }
+    for mov in all_valid_moves(board) {
+        let undo_info = board.make_move(mov);
+        walk(depth - 1, board, check);
+        board.unmake_move(mov, undo_info);
+    }
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use board_representation::chess_board::ChessBoard;
+use common::{PERFT_POSITIONS, for_each_node};
+use moves_generation::chess_move::move_piece_uci;
+use std::str::FromStr;
+
+fn play(fen: &str, moves: &[&str]) -> ChessBoard {
+    let mut board = ChessBoard::from_str(fen).unwrap();
+    moves
+        .iter()
+        .for_each(|uci| move_piece_uci(&mut board, uci).unwrap());
+    board
+}
+
+#[cfg(test)]
+mod fen {
+    use super::*;
+
+    #[test]
+    fn round_trip_along_perft_trees() {
+        for_each_node(3, |board| {
+            let fen = board.to_fen();
+            assert_eq!(ChessBoard::from_str(&fen).unwrap(), *board, "{}", fen);
+        });
+    }
+
+    #[test]
+    fn clocks_after_quiet_moves() {
+        let board = play(PERFT_POSITIONS[0], &["e2e4", "e7e5", "g1f3"]);
+        assert_eq!(
+            board.to_fen(),
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+        );
+
+        let board = play(PERFT_POSITIONS[0], &["g1f3", "g8f6", "f3g1", "f6g8"]);
+        assert_eq!(
+            board.to_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 4 3"
+        );
+    }
+
+    #[test]
+    fn clocks_reset_by_captures_and_pawn_moves() {
+        let board = play(
+            PERFT_POSITIONS[0],
+            &["g1f3", "d7d5", "b1c3", "g8f6", "c3d5"],
+        );
+        assert_eq!(board.halfmove_clock(), 0);
+        assert_eq!(board.fullmove_number(), 3);
+
+        let board = play(PERFT_POSITIONS[0], &["g1f3", "g8f6", "e2e3"]);
+        assert_eq!(board.halfmove_clock(), 0);
+        assert_eq!(board.fullmove_number(), 2);
+    }
+
+    #[test]
+    fn en_passant_target_square_after_double_push() {
+        let board = play(PERFT_POSITIONS[0], &["e2e4"]);
+        assert_eq!(
+            board.to_fen(),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+    }
+}
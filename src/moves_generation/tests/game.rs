@@ -0,0 +1,197 @@
+use board_representation::chess_board::ChessBoard;
+use board_representation::chess_piece::Color;
+use moves_generation::game::{IllegalMoveError, has_insufficient_material};
+use moves_generation::{Game, GameStatus, Move};
+use std::str::FromStr;
+
+fn game(fen: &str) -> Game {
+    Game::new(ChessBoard::from_str(fen).unwrap())
+}
+
+fn play(game: &mut Game, uci_moves: &[&str]) {
+    for uci in uci_moves {
+        let mov = Move::from_uci_string(uci, game.board()).unwrap();
+        game.play(mov).unwrap();
+    }
+}
+
+fn insufficient(fen: &str) -> bool {
+    has_insufficient_material(&ChessBoard::from_str(fen).unwrap())
+}
+
+const KNIGHTS_DANCE: [&str; 4] = ["g1f3", "g8f6", "f3g1", "f6g8"];
+
+#[cfg(test)]
+mod game {
+    use super::*;
+
+    #[test]
+    fn new_game_is_ongoing() {
+        let game = Game::default();
+        assert_eq!(game.status(), GameStatus::Ongoing);
+        assert!(!game.status().is_over());
+        assert_eq!(game.repetitions(), 1);
+        assert_eq!(game.moves().count(), 0);
+    }
+
+    #[test]
+    fn checkmate() {
+        let mut game = Game::default();
+        play(&mut game, &["f2f3", "e7e5", "g2g4", "d8h4"]);
+        assert_eq!(
+            game.status(),
+            GameStatus::Checkmate {
+                winner: Color::Black
+            }
+        );
+        assert!(game.status().is_over());
+        assert!(!game.status().is_draw());
+    }
+
+    #[test]
+    fn stalemate() {
+        let game = game("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+        assert_eq!(game.status(), GameStatus::Stalemate);
+        assert!(game.status().is_draw());
+    }
+
+    #[test]
+    fn threefold_and_fivefold_repetition() {
+        let mut game = Game::default();
+        play(&mut game, &KNIGHTS_DANCE);
+        assert_eq!(game.repetitions(), 2);
+        assert_eq!(game.status(), GameStatus::Ongoing);
+        play(&mut game, &KNIGHTS_DANCE);
+        assert_eq!(game.repetitions(), 3);
+        assert_eq!(game.status(), GameStatus::ThreefoldRepetition);
+        play(&mut game, &KNIGHTS_DANCE);
+        assert_eq!(game.status(), GameStatus::ThreefoldRepetition);
+        play(&mut game, &KNIGHTS_DANCE);
+        assert_eq!(game.repetitions(), 5);
+        assert_eq!(game.status(), GameStatus::FivefoldRepetition);
+    }
+
+    #[test]
+    fn repetitions_stop_at_irreversible_moves() {
+        let mut game = Game::default();
+        play(&mut game, &KNIGHTS_DANCE);
+        play(&mut game, &["e2e4", "e7e5"]);
+        play(&mut game, &KNIGHTS_DANCE);
+        assert_eq!(game.repetitions(), 2);
+    }
+
+    #[test]
+    fn en_passant_square_counts_only_when_capturing_is_legal() {
+        let mut game = game("4k3/8/8/8/3p4/8/4P3/4K2N w - - 0 1");
+        play(&mut game, &["e2e4"]);
+        let capture_possible = *game.board();
+        play(&mut game, &["e8d8", "h1g3", "d8e8", "g3h1"]);
+        // Same pieces, but the en passant capture is gone.
+        assert_eq!(
+            game.board().pieces_of(Color::White),
+            capture_possible.pieces_of(Color::White)
+        );
+        assert_eq!(game.repetitions(), 1);
+    }
+
+    #[test]
+    fn castling_rights_make_positions_different() {
+        let mut game = game("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        play(&mut game, &["e1f1", "e8f8", "f1e1", "f8e8"]);
+        assert_eq!(game.repetitions(), 1);
+        play(&mut game, &["e1f1", "e8f8", "f1e1", "f8e8"]);
+        assert_eq!(game.repetitions(), 2);
+    }
+
+    #[test]
+    fn fifty_and_seventy_five_move_rules() {
+        let mut game = game("4k3/8/8/8/8/8/4P3/R3K3 w - - 98 80");
+        play(&mut game, &["a1a2"]);
+        assert_eq!(game.status(), GameStatus::Ongoing);
+        play(&mut game, &["e8d8"]);
+        assert_eq!(game.status(), GameStatus::FiftyMoveRule);
+        play(&mut game, &["e2e4"]);
+        assert_eq!(game.status(), GameStatus::Ongoing);
+
+        let game = super::game("4k3/8/8/8/8/8/4P3/R3K3 w - - 150 100");
+        assert_eq!(game.status(), GameStatus::SeventyFiveMoveRule);
+    }
+
+    #[test]
+    fn checkmate_beats_the_fifty_move_rule() {
+        let mut game = game("k7/8/1K6/8/8/8/8/7R w - - 99 80");
+        play(&mut game, &["h1h8"]);
+        assert_eq!(
+            game.status(),
+            GameStatus::Checkmate {
+                winner: Color::White
+            }
+        );
+    }
+
+    #[test]
+    fn insufficient_material() {
+        assert!(insufficient("4k3/8/8/8/8/8/8/4K3 w - - 0 1"));
+        assert!(insufficient("4k3/8/8/8/8/8/8/4KN2 w - - 0 1"));
+        assert!(insufficient("4kb2/8/8/8/8/8/8/4K3 w - - 0 1"));
+        // Bishops on the same color.
+        assert!(insufficient("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+        assert!(insufficient("4k1b1/8/8/8/8/8/8/1B2K3 w - - 0 1"));
+        assert!(!insufficient("4k1b1/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+        assert!(!insufficient("4k3/8/8/8/8/8/8/3NKN2 w - - 0 1"));
+        assert!(!insufficient("4kn2/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+        assert!(!insufficient("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"));
+        assert!(!insufficient("4k3/8/8/8/8/8/8/R3K3 w - - 0 1"));
+        assert_eq!(
+            game("4k3/8/8/8/8/8/8/4KN2 w - - 0 1").status(),
+            GameStatus::InsufficientMaterial
+        );
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut game = Game::default();
+        play(&mut game, &["e2e4", "e7e5", "g1f3"]);
+        let after_three_moves = *game.board();
+
+        assert_eq!(
+            game.undo().map(|mov| mov.to_uci_string()).as_deref(),
+            Some("g1f3")
+        );
+        assert_eq!(
+            game.undo().map(|mov| mov.to_uci_string()).as_deref(),
+            Some("e7e5")
+        );
+        assert_eq!(game.moves().count(), 1);
+        assert_eq!(
+            game.redo().map(|mov| mov.to_uci_string()).as_deref(),
+            Some("e7e5")
+        );
+        assert_eq!(
+            game.redo().map(|mov| mov.to_uci_string()).as_deref(),
+            Some("g1f3")
+        );
+        assert_eq!(game.redo(), None);
+        assert_eq!(*game.board(), after_three_moves);
+
+        while game.undo().is_some() {}
+        assert_eq!(game.board(), game.starting_board());
+        // Playing a new move forgets the moves taken back.
+        play(&mut game, &["d2d4"]);
+        assert_eq!(game.redo(), None);
+    }
+
+    #[test]
+    fn illegal_moves_are_rejected() {
+        let mut game = Game::default();
+        let mov = Move::from_uci_string("e2e5", game.board()).unwrap();
+        assert_eq!(
+            game.play(mov),
+            Err(IllegalMoveError {
+                mov,
+                fen: ChessBoard::default().to_fen()
+            })
+        );
+        assert_eq!(*game.board(), ChessBoard::default());
+    }
+}
@@ -0,0 +1,116 @@
+use board_representation::chess_board::ChessBoard;
+use moves_generation::valid_moves::all_valid_moves;
+use std::str::FromStr;
+
+fn legal_moves(fen: &str) -> Vec<String> {
+    let board = ChessBoard::from_str(fen).unwrap();
+    let mut moves = all_valid_moves(&board)
+        .iter()
+        .map(|mov| mov.to_uci_string())
+        .collect::<Vec<_>>();
+    moves.sort();
+    moves
+}
+
+fn legal_moves_from(fen: &str, from: &str) -> Vec<String> {
+    legal_moves(fen)
+        .into_iter()
+        .filter(|mov| mov.starts_with(from))
+        .collect()
+}
+
+#[cfg(test)]
+mod legal_moves {
+    use super::*;
+
+    mod pins {
+        use super::*;
+
+        #[test]
+        fn pinned_rook_moves_along_the_pin() {
+            let moves = legal_moves_from("4k3/4r3/8/8/8/8/4R3/4K3 w - - 0 1", "e2");
+            assert_eq!(moves, vec!["e2e3", "e2e4", "e2e5", "e2e6", "e2e7"]);
+        }
+
+        #[test]
+        fn pinned_knight_cannot_move() {
+            assert!(legal_moves_from("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1", "e2").is_empty());
+        }
+
+        #[test]
+        fn pinned_pawn_captures_the_pinner() {
+            let moves = legal_moves_from("4k3/8/8/8/8/2b5/3P4/4K3 w - - 0 1", "d2");
+            assert_eq!(moves, vec!["d2c3"]);
+        }
+
+        #[test]
+        fn piece_behind_another_piece_is_not_pinned() {
+            let moves = legal_moves_from("4k3/4r3/8/8/4P3/8/4R3/4K3 w - - 0 1", "e2");
+            assert_eq!(moves.len(), 8);
+        }
+    }
+
+    mod en_passant {
+        use super::*;
+
+        #[test]
+        fn discovered_check_along_the_rank() {
+            // Both pawns leave the fifth rank, exposing the king to the rook.
+            let moves = legal_moves("8/8/8/K2pP2r/8/8/8/7k w - d6 0 1");
+            assert!(!moves.contains(&"e5d6".to_string()));
+            let moves = legal_moves("8/8/8/K2pP3/8/8/8/7k w - d6 0 1");
+            assert!(moves.contains(&"e5d6".to_string()));
+        }
+
+        #[test]
+        fn capturing_the_checking_pawn() {
+            let moves = legal_moves_from("8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1", "e4");
+            assert_eq!(moves, vec!["e4d3"]);
+        }
+
+        #[test]
+        fn pinned_along_the_diagonal() {
+            // Capturing stays on the pin, pushing leaves it.
+            let moves = legal_moves_from("kb6/8/8/3pP3/8/8/7K/8 w - d6 0 1", "e5");
+            assert_eq!(moves, vec!["e5d6"]);
+            let moves = legal_moves_from("k6b/8/8/3pP3/8/8/8/K7 w - d6 0 1", "e5");
+            assert!(moves.is_empty());
+            let moves = legal_moves_from("k7/8/8/3pP3/8/8/8/K7 w - d6 0 1", "e5");
+            assert_eq!(moves, vec!["e5d6", "e5e6"]);
+        }
+    }
+
+    mod evasions {
+        use super::*;
+
+        #[test]
+        fn block_or_step_away() {
+            // The king cannot step back along the rank of the check.
+            let moves = legal_moves("4k3/8/8/8/8/2N5/8/r3K3 w - - 0 1");
+            assert_eq!(moves, vec!["c3b1", "c3d1", "e1d2", "e1e2", "e1f2"]);
+        }
+
+        #[test]
+        fn capture_the_checker() {
+            let moves = legal_moves("4k3/8/8/8/8/5n2/8/4KR2 w - - 0 1");
+            assert_eq!(moves, vec!["e1d1", "e1e2", "e1f2", "f1f3"]);
+        }
+
+        #[test]
+        fn double_check_only_moves_the_king() {
+            let moves = legal_moves("4k3/8/8/3N4/1b6/8/8/r3K2R w K - 0 1");
+            assert!(!moves.is_empty());
+            assert!(moves.iter().all(|mov| mov.starts_with("e1")));
+            assert!(!moves.contains(&"e1g1".to_string()));
+        }
+
+        #[test]
+        fn checkmate() {
+            assert!(legal_moves("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").is_empty());
+            assert!(
+                legal_moves("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")
+                    .is_empty()
+            );
+        }
+    }
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use board_representation::chess_board::ChessBoard;
+use board_representation::chess_piece::{BLACK_KNIGHT, BLACK_PAWN};
+use common::for_each_node;
+use moves_generation::chess_move::{MakeMove, Move};
+use moves_generation::valid_moves::all_valid_moves;
+use std::str::FromStr;
+
+/// Plays `uci` on `fen`, checking that unmaking it gives back the starting position.
+fn make(fen: &str, uci: &str) -> ChessBoard {
+    let board = ChessBoard::from_str(fen).unwrap();
+    let mov = Move::from_uci_string(uci, &board).unwrap();
+    let mut board_after_move = board;
+    let undo_info = board_after_move.make_move(mov);
+    let mut board_after_unmake = board_after_move;
+    board_after_unmake.unmake_move(mov, undo_info);
+    assert_eq!(board_after_unmake, board);
+    board_after_move
+}
+
+#[cfg(test)]
+mod make_unmake {
+    use super::*;
+
+    #[test]
+    fn unmake_restores_state_along_perft_trees() {
+        // Every unmake restores the exact state, every make agrees with a from-scratch
+        // reconstruction of the position.
+        for_each_node(2, |board| {
+            for mov in all_valid_moves(board) {
+                let before = *board;
+                let undo_info = board.make_move(mov);
+                assert_eq!(board.hash(), board.compute_hash());
+                assert_eq!(ChessBoard::from_str(&board.to_fen()).unwrap(), *board);
+                board.unmake_move(mov, undo_info);
+                assert_eq!(
+                    *board,
+                    before,
+                    "{} {}",
+                    before.to_fen(),
+                    mov.to_uci_string()
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn undo_info_of_capture() {
+        let mut board = ChessBoard::from_str(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 3 1",
+        )
+        .unwrap();
+        let undo_info = board.make_move(Move::from_uci_string("e5d7", &board).unwrap());
+        assert_eq!(undo_info.captured_piece, Some(BLACK_PAWN));
+        assert_eq!(undo_info.halfmove_clock, 3);
+        assert_eq!(undo_info.castling_rights, 0b1111);
+        assert_eq!(undo_info.en_passant_target_square, None);
+    }
+
+    #[test]
+    fn en_passant() {
+        let board_after_move = make(
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            "e5f6",
+        );
+        assert_eq!(
+            board_after_move.to_fen(),
+            "rnbqkbnr/ppp1p1pp/5P2/3p4/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3"
+        );
+    }
+
+    #[test]
+    fn castling() {
+        let board_after_move = make("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 5 20", "e8c8");
+        assert_eq!(
+            board_after_move.to_fen(),
+            "2kr3r/8/8/8/8/8/8/R3K2R w KQ - 6 21"
+        );
+    }
+
+    #[test]
+    fn capture_promotion() {
+        let mut board = ChessBoard::from_str("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let mov = Move::from_uci_string("a7b8n", &board).unwrap();
+        let undo_info = board.make_move(mov);
+        assert_eq!(board.to_fen(), "1N2k3/8/8/8/8/8/8/4K3 b - - 0 1");
+        assert_eq!(undo_info.captured_piece, Some(BLACK_KNIGHT));
+
+        board.unmake_move(mov, undo_info);
+        assert_eq!(board.to_fen(), "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+    }
+}
@@ -0,0 +1,89 @@
+use board_representation::chess_board::ChessBoard;
+use board_representation::square::Square;
+use moves_generation::chess_move::Move;
+use moves_generation::move_list::{MAX_MOVES, MoveList};
+use moves_generation::valid_moves::all_valid_moves;
+use std::str::FromStr;
+
+fn uci(moves: &[Move]) -> Vec<String> {
+    moves.iter().map(|mov| mov.to_uci_string()).collect()
+}
+
+fn list(moves: &[&str]) -> MoveList {
+    moves
+        .iter()
+        .map(|mov| Move::from_uci_string(mov, &ChessBoard::default()).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod move_list {
+    use super::*;
+
+    #[test]
+    fn push_and_index() {
+        let mut moves = MoveList::new();
+        assert!(moves.is_empty());
+        moves.push(Move::quiet(Square::E2, Square::E4));
+        moves.push(Move::quiet(Square::G1, Square::F3));
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[1], Move::quiet(Square::G1, Square::F3));
+        assert_eq!(moves.first(), Some(&Move::quiet(Square::E2, Square::E4)));
+        assert!(moves.contains(&Move::quiet(Square::G1, Square::F3)));
+    }
+
+    #[test]
+    fn iteration() {
+        let moves = list(&["e2e4", "g1f3", "d2d4"]);
+        assert_eq!(uci(&moves), vec!["e2e4", "g1f3", "d2d4"]);
+        assert_eq!((&moves).into_iter().count(), 3);
+        let owned = moves.clone().into_iter();
+        assert_eq!(owned.len(), 3);
+        assert_eq!(owned.collect::<Vec<_>>(), moves.to_vec());
+    }
+
+    #[test]
+    fn swap_remove() {
+        let mut moves = list(&["e2e4", "g1f3", "d2d4", "c2c4"]);
+        assert_eq!(moves.swap_remove(1).to_uci_string(), "g1f3");
+        assert_eq!(uci(&moves), vec!["e2e4", "c2c4", "d2d4"]);
+        assert_eq!(moves.swap_remove(2).to_uci_string(), "d2d4");
+        assert_eq!(uci(&moves), vec!["e2e4", "c2c4"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_remove_out_of_bounds() {
+        list(&["e2e4"]).swap_remove(1);
+    }
+
+    #[test]
+    fn sort_by_score() {
+        let mut moves = list(&["a2a3", "e2e4", "b1c3", "d2d4", "g1f3"]);
+        moves.sort_by_score(|mov| match mov.to_uci_string().as_str() {
+            "e2e4" | "d2d4" => 10,
+            "a2a3" => -5,
+            _ => 0,
+        });
+        // Ties keep the generation order.
+        assert_eq!(uci(&moves), vec!["e2e4", "d2d4", "b1c3", "g1f3", "a2a3"]);
+    }
+
+    #[test]
+    fn slice_methods() {
+        let mut moves = list(&["g1f3", "e2e4", "b1c3"]);
+        moves.sort_by_key(|mov| mov.from());
+        assert_eq!(uci(&moves), vec!["b1c3", "g1f3", "e2e4"]);
+        moves.clear();
+        assert!(moves.is_empty());
+    }
+
+    #[test]
+    fn holds_the_position_with_the_most_moves() {
+        let board =
+            ChessBoard::from_str("R6R/3Q4/1Q4Q1/4Q3/2Q4Q/Q4Q2/pp1Q4/kBNN1KB1 w - - 0 1").unwrap();
+        let moves = all_valid_moves(&board);
+        assert_eq!(moves.len(), 218);
+        assert!(moves.len() <= MAX_MOVES);
+    }
+}
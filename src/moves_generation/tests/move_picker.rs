@@ -0,0 +1,232 @@
+mod common;
+
+use board_representation::chess_board::ChessBoard;
+use board_representation::chess_piece::Color;
+use common::PERFT_POSITIONS;
+use moves_generation::chess_move::Move;
+use moves_generation::move_picker::{HISTORY_MAX, MoveHistory, MovePicker, is_quiet};
+use moves_generation::valid_moves::all_valid_moves;
+use std::str::FromStr;
+
+fn board(fen: &str) -> ChessBoard {
+    ChessBoard::from_str(fen).unwrap()
+}
+
+fn uci_move(board: &ChessBoard, uci: &str) -> Move {
+    Move::from_uci_string(uci, board).unwrap()
+}
+
+fn picked(picker: MovePicker) -> Vec<String> {
+    picker.map(|mov| mov.to_uci_string()).collect()
+}
+
+/// Material from the point of view of the side to move.
+fn material(board: &ChessBoard) -> i32 {
+    let pieces = [
+        (board.pawns, 1),
+        (board.knights, 3),
+        (board.bishops, 3),
+        (board.rooks, 5),
+        (board.queens, 9),
+    ];
+    let score = pieces
+        .iter()
+        .map(|(kind, value)| {
+            value
+                * ((*kind & board.white_pieces).count() as i32
+                    - (*kind & board.black_pieces).count() as i32)
+        })
+        .sum::<i32>();
+    match board.current_turn() {
+        Color::White => score,
+        Color::Black => -score,
+    }
+}
+
+/// A plain alpha-beta search on material, ordering moves with the picker when there is a
+/// move history and in generation order otherwise.
+struct Bench {
+    move_history: Option<MoveHistory>,
+    nodes: u64,
+}
+
+impl Bench {
+    fn alpha_beta(
+        &mut self,
+        board: &ChessBoard,
+        depth: u8,
+        ply: usize,
+        mut alpha: i32,
+        beta: i32,
+        previous_move: Option<Move>,
+    ) -> i32 {
+        self.nodes += 1;
+        if depth == 0 {
+            return material(board);
+        }
+        let moves = match &self.move_history {
+            Some(move_history) => {
+                MovePicker::new(board, None, ply, previous_move, move_history).collect::<Vec<_>>()
+            }
+            None => all_valid_moves(board).to_vec(),
+        };
+        if moves.is_empty() {
+            return -1000;
+        }
+
+        let mut tried_quiets = Vec::new();
+        for mov in moves {
+            let mut board_after_move = *board;
+            mov.move_piece(&mut board_after_move);
+            let score = -self.alpha_beta(
+                &board_after_move,
+                depth - 1,
+                ply + 1,
+                -beta,
+                -alpha,
+                Some(mov),
+            );
+            if score >= beta {
+                if let Some(move_history) = &mut self.move_history
+                    && is_quiet(mov)
+                {
+                    let color = board.current_turn();
+                    move_history.record_cutoff(
+                        color,
+                        ply,
+                        depth,
+                        mov,
+                        previous_move,
+                        &tried_quiets,
+                    );
+                }
+                return score;
+            }
+            alpha = alpha.max(score);
+            if is_quiet(mov) {
+                tried_quiets.push(mov);
+            }
+        }
+        alpha
+    }
+}
+
+#[cfg(test)]
+mod move_picker {
+    use super::*;
+
+    #[test]
+    fn yields_every_legal_move_once() {
+        let move_history = MoveHistory::new();
+        for fen in PERFT_POSITIONS {
+            let board = board(fen);
+            let picker = MovePicker::new(&board, None, 0, None, &move_history);
+            assert_eq!(picker.len(), all_valid_moves(&board).len());
+            let mut moves = picked(picker);
+            let mut legal = all_valid_moves(&board)
+                .iter()
+                .map(|mov| mov.to_uci_string())
+                .collect::<Vec<_>>();
+            moves.sort();
+            legal.sort();
+            assert_eq!(moves, legal, "{}", fen);
+        }
+    }
+
+    #[test]
+    fn hash_move_first() {
+        let board = board(PERFT_POSITIONS[1]);
+        let hash_move = uci_move(&board, "a2a3");
+        let mut picker = MovePicker::new(&board, Some(hash_move), 0, None, &MoveHistory::new());
+        assert_eq!(picker.next(), Some(hash_move));
+    }
+
+    #[test]
+    fn mvv_lva() {
+        // The queen on d5 is taken by the pawn before the knight and the rook.
+        let board = board("4k3/8/2r5/3q1p2/4P3/2N2N2/8/3RK3 w - - 0 1");
+        let moves = picked(MovePicker::new(&board, None, 0, None, &MoveHistory::new()));
+        assert_eq!(moves[..4], ["e4d5", "c3d5", "d1d5", "e4f5"]);
+    }
+
+    #[test]
+    fn promotions_with_captures() {
+        let board = board("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+        let moves = picked(MovePicker::new(&board, None, 0, None, &MoveHistory::new()));
+        assert_eq!(moves[..3], ["a7b8q", "a7a8q", "a7b8r"]);
+    }
+
+    #[test]
+    fn killers_and_countermoves() {
+        let board = ChessBoard::default();
+        let previous_move = uci_move(&board, "e2e4");
+        let (killer, older_killer, countermove) = (
+            uci_move(&board, "b1c3"),
+            uci_move(&board, "h2h3"),
+            uci_move(&board, "a2a4"),
+        );
+        let mut move_history = MoveHistory::new();
+        move_history.record_cutoff(Color::White, 3, 1, older_killer, None, &[]);
+        move_history.record_cutoff(Color::White, 3, 1, killer, None, &[]);
+        move_history.record_cutoff(Color::White, 5, 3, countermove, Some(previous_move), &[]);
+        assert_eq!(move_history.killers(3), [killer, older_killer]);
+        assert_eq!(move_history.countermove(previous_move), Some(countermove));
+
+        let moves = picked(MovePicker::new(
+            &board,
+            None,
+            3,
+            Some(previous_move),
+            &move_history,
+        ));
+        assert_eq!(moves[..3], ["b1c3", "h2h3", "a2a4"]);
+        // Killers belong to their ply, elsewhere the history of the moves orders them.
+        let moves = picked(MovePicker::new(&board, None, 4, None, &move_history));
+        assert_eq!(moves[0], "a2a4");
+    }
+
+    #[test]
+    fn history() {
+        let board = ChessBoard::default();
+        let (good, bad) = (uci_move(&board, "g1f3"), uci_move(&board, "g2g4"));
+        let mut move_history = MoveHistory::new();
+        for depth in 1..=200 {
+            // Killers are kept at another ply than the one the moves are picked for.
+            move_history.record_cutoff(Color::White, 1, depth, good, None, &[bad]);
+        }
+        assert!(move_history.history(Color::White, good) <= HISTORY_MAX);
+        assert!(move_history.history(Color::White, bad) >= -HISTORY_MAX);
+        assert_eq!(move_history.history(Color::Black, good), 0);
+
+        let moves = picked(MovePicker::new(&board, None, 0, None, &move_history));
+        assert_eq!(moves.first().unwrap(), "g1f3");
+        assert_eq!(moves.last().unwrap(), "g2g4");
+
+        move_history.clear();
+        assert_eq!(move_history.history(Color::White, good), 0);
+    }
+
+    #[test]
+    fn node_count_reduction() {
+        let depth = 4;
+        let (mut unordered, mut ordered) = (0, 0);
+        for fen in PERFT_POSITIONS {
+            let board = board(fen);
+            let mut bench = Bench {
+                move_history: None,
+                nodes: 0,
+            };
+            let score = bench.alpha_beta(&board, depth, 0, -10000, 10000, None);
+            unordered += bench.nodes;
+
+            let mut bench = Bench {
+                move_history: Some(MoveHistory::new()),
+                nodes: 0,
+            };
+            let ordered_score = bench.alpha_beta(&board, depth, 0, -10000, 10000, None);
+            ordered += bench.nodes;
+            assert_eq!(score, ordered_score, "{}", fen);
+        }
+        assert!(ordered * 2 < unordered);
+    }
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use board_representation::chess_board::ChessBoard;
+use common::for_each_node;
+use moves_generation::Move;
+use moves_generation::san::SanParseError;
+use moves_generation::valid_moves::all_valid_moves;
+use std::str::FromStr;
+
+fn san(fen: &str, uci: &str) -> String {
+    let board = ChessBoard::from_str(fen).unwrap();
+    Move::from_uci_string(uci, &board).unwrap().to_san(&board)
+}
+
+fn parse(fen: &str, san: &str) -> Result<String, SanParseError> {
+    let board = ChessBoard::from_str(fen).unwrap();
+    Move::from_san(&board, san).map(|mov| mov.to_uci_string())
+}
+
+#[cfg(test)]
+mod san {
+    use super::*;
+
+    const KNIGHTS: &str = "4k3/8/8/8/8/8/8/1N2KN1N w - - 0 1";
+    const ROOKS: &str = "R7/7k/8/8/8/8/8/R3K3 w - - 0 1";
+
+    #[test]
+    fn pawn_moves() {
+        let fen = "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2";
+        assert_eq!(san(fen, "e4e5"), "e5");
+        assert_eq!(san(fen, "e4d5"), "exd5");
+        assert_eq!(san("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), "exd6");
+    }
+
+    #[test]
+    fn piece_moves() {
+        let fen = "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2";
+        assert_eq!(san(fen, "g1f3"), "Nf3");
+        assert_eq!(san(fen, "f1b5"), "Bb5+");
+        assert_eq!(san(fen, "e1e2"), "Ke2");
+    }
+
+    #[test]
+    fn disambiguation() {
+        // Knights on b1, f1 and h1 can reach d2 and g3.
+        assert_eq!(san(KNIGHTS, "b1d2"), "Nbd2");
+        assert_eq!(san(KNIGHTS, "f1d2"), "Nfd2");
+        assert_eq!(san(KNIGHTS, "f1g3"), "Nfg3");
+        assert_eq!(san(KNIGHTS, "b1c3"), "Nc3");
+        // Rooks on the same file disambiguate by rank.
+        assert_eq!(san(ROOKS, "a1a4"), "R1a4");
+        assert_eq!(san(ROOKS, "a8a4"), "R8a4");
+        // Three queens need the full origin square.
+        let queens = "1k6/8/8/8/4Q2Q/8/8/K6Q w - - 0 1";
+        assert_eq!(san(queens, "h4e1"), "Qh4e1");
+        assert_eq!(san(queens, "e4e1"), "Qee1");
+        assert_eq!(san(queens, "h1e1"), "Q1e1");
+    }
+
+    #[test]
+    fn pinned_piece_does_not_need_disambiguation() {
+        // The knight on e2 is pinned and cannot reach c3.
+        let fen = "4r1k1/8/8/8/8/8/4N3/1N2K3 w - - 0 1";
+        assert_eq!(san(fen, "b1c3"), "Nc3");
+    }
+
+    #[test]
+    fn promotions() {
+        let fen = "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+        assert_eq!(san(fen, "a7a8q"), "a8=Q");
+        assert_eq!(san(fen, "a7a8r"), "a8=R");
+        assert_eq!(san(fen, "a7b8n"), "axb8=N");
+        assert_eq!(parse(fen, "a8=R"), Ok("a7a8r".to_string()));
+        assert_eq!(parse(fen, "a8Q"), Ok("a7a8q".to_string()));
+        assert_eq!(parse(fen, "axb8=B"), Ok("a7b8b".to_string()));
+        assert_eq!(
+            parse(fen, "a8"),
+            Err(SanParseError::IllegalMove {
+                found: "a8".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn castling() {
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        assert_eq!(san(fen, "e1g1"), "O-O");
+        assert_eq!(san(fen, "e1c1"), "O-O-O");
+        assert_eq!(parse(fen, "O-O"), Ok("e1g1".to_string()));
+        assert_eq!(parse(fen, "0-0-0"), Ok("e1c1".to_string()));
+        assert_eq!(san("5k2/8/8/8/8/8/8/4K2R w K - 0 1", "e1g1"), "O-O+");
+    }
+
+    #[test]
+    fn checkmate() {
+        let fen = "rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2";
+        assert_eq!(san(fen, "d8h4"), "Qh4#");
+        assert_eq!(parse(fen, "Qh4#"), Ok("d8h4".to_string()));
+    }
+
+    #[test]
+    fn sloppy_input() {
+        assert_eq!(parse(KNIGHTS, "Nbd2"), Ok("b1d2".to_string()));
+        assert_eq!(parse(KNIGHTS, "Nb1c3"), Ok("b1c3".to_string()));
+        assert_eq!(parse(KNIGHTS, "Nbc3"), Ok("b1c3".to_string()));
+        assert_eq!(parse(KNIGHTS, "N1-c3"), Ok("b1c3".to_string()));
+        let fen = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+        assert_eq!(parse(fen, "exd6 e.p."), Ok("e5d6".to_string()));
+        assert_eq!(parse(fen, "exd6ep"), Ok("e5d6".to_string()));
+        assert_eq!(parse(fen, "ed6"), Ok("e5d6".to_string()));
+        assert_eq!(parse(fen, " e6!? "), Ok("e5e6".to_string()));
+        assert_eq!(parse(fen, "e5e6"), Ok("e5e6".to_string()));
+    }
+
+    #[test]
+    fn invalid_san() {
+        let found = |san: &str| san.to_string();
+        assert_eq!(
+            parse(KNIGHTS, "Nd2"),
+            Err(SanParseError::AmbiguousMove {
+                found: found("Nd2")
+            })
+        );
+        assert_eq!(
+            parse(KNIGHTS, "Nd4"),
+            Err(SanParseError::IllegalMove {
+                found: found("Nd4")
+            })
+        );
+        for san in ["", "N", "Zd2", "Nd9", "Nbbd2", "e8=K", "O-O-O-O"] {
+            assert_eq!(
+                parse(KNIGHTS, san),
+                Err(SanParseError::InvalidSyntax { found: found(san) })
+            );
+        }
+    }
+
+    #[test]
+    fn san_round_trip_along_perft_trees() {
+        // Every legal move has a distinct SAN string that parses back to the move.
+        for_each_node(2, |board| {
+            let moves = all_valid_moves(board);
+            let mut sans = moves
+                .iter()
+                .map(|mov| mov.to_san(board))
+                .collect::<Vec<_>>();
+            for (mov, san) in moves.iter().zip(&sans) {
+                assert_eq!(Move::from_san(board, san), Ok(*mov), "{}", board.to_fen());
+            }
+            sans.sort();
+            sans.dedup();
+            assert_eq!(sans.len(), moves.len(), "{}", board.to_fen());
+        });
+    }
+}
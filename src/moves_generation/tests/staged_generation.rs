@@ -0,0 +1,151 @@
+mod common;
+
+use board_representation::chess_board::{ChessBoard, SquareContent};
+use board_representation::chess_piece::PieceKind;
+use board_representation::square::Square;
+use common::for_each_node;
+use moves_generation::chess_move::{MakeMove, Move};
+use moves_generation::valid_moves::{
+    Captures, Evasions, QuietChecks, Quiets, all_valid_moves, generate, gives_check, is_check,
+};
+use std::str::FromStr;
+
+fn sorted(moves: impl IntoIterator<Item = Move>) -> Vec<String> {
+    let mut moves = moves
+        .into_iter()
+        .map(|mov| mov.to_uci_string())
+        .collect::<Vec<_>>();
+    moves.sort();
+    moves
+}
+
+fn is_capture_or_promotion(board: &ChessBoard, mov: Move) -> bool {
+    let is_en_passant = Some(mov.to()) == board.en_passant_target_square()
+        && board.piece_at_source_or_panic(mov.from()).kind == PieceKind::Pawn;
+    // Castling is encoded as the king taking its own rook.
+    board.at(mov.destination()) != SquareContent::Empty
+        || is_en_passant
+        || mov.promoted_piece_kind().is_some()
+}
+
+fn gives_check_when_played(board: &ChessBoard, mov: Move) -> bool {
+    let mut board_after_move = *board;
+    board_after_move.make_move(mov);
+    is_check(&board_after_move)
+}
+
+/// Checks every generation mode against `all_valid_moves`.
+fn assert_stages(board: &mut ChessBoard) {
+    let all_moves = all_valid_moves(board);
+    let captures = generate::<Captures>(board);
+    let quiets = generate::<Quiets>(board);
+    let fen = board.to_fen();
+
+    assert_eq!(captures.len() + quiets.len(), all_moves.len(), "{}", fen);
+    assert_eq!(
+        sorted(captures.iter().chain(quiets.iter()).copied()),
+        sorted(all_moves.iter().copied()),
+        "{}",
+        fen
+    );
+    assert!(
+        captures
+            .iter()
+            .all(|mov| is_capture_or_promotion(board, *mov))
+    );
+    assert!(
+        quiets
+            .iter()
+            .all(|mov| !is_capture_or_promotion(board, *mov))
+    );
+
+    let evasions = generate::<Evasions>(board);
+    if is_check(board) {
+        assert_eq!(
+            sorted(evasions),
+            sorted(all_moves.iter().copied()),
+            "{}",
+            fen
+        );
+    } else {
+        assert!(evasions.is_empty(), "{}", fen);
+    }
+
+    for mov in &all_moves {
+        assert_eq!(
+            gives_check(board, *mov),
+            gives_check_when_played(board, *mov),
+            "{} {}",
+            fen,
+            mov
+        );
+    }
+    let quiet_checks = quiets
+        .iter()
+        .copied()
+        .filter(|mov| gives_check_when_played(board, *mov));
+    assert_eq!(
+        sorted(generate::<QuietChecks>(board)),
+        sorted(quiet_checks),
+        "{}",
+        fen
+    );
+}
+
+#[cfg(test)]
+mod staged_generation {
+    use super::*;
+
+    #[test]
+    fn stages_match_all_valid_moves_along_perft_trees() {
+        for_each_node(2, assert_stages);
+    }
+
+    #[test]
+    fn quiet_promotions_are_captures() {
+        let board = ChessBoard::from_str("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        assert_eq!(
+            sorted(generate::<Captures>(&board)),
+            vec!["a7a8b", "a7a8n", "a7a8q", "a7a8r"]
+        );
+        assert!(
+            generate::<Quiets>(&board)
+                .iter()
+                .all(|mov| mov.to_uci_string().starts_with("a1"))
+        );
+    }
+
+    #[test]
+    fn castling_is_quiet() {
+        let board = ChessBoard::from_str("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        let quiets = sorted(generate::<Quiets>(&board));
+        assert!(quiets.contains(&"e1g1".to_string()));
+        assert!(quiets.contains(&"e1c1".to_string()));
+        assert!(
+            generate::<Captures>(&board)
+                .iter()
+                .all(|mov| mov.to() == Square::A8 || mov.to() == Square::H8)
+        );
+    }
+
+    #[test]
+    fn quiet_checks() {
+        // Every knight move uncovers the rook, c7 uncovers the bishop.
+        let board = ChessBoard::from_str("4k3/8/2P5/1B6/4N3/8/8/4R2K w - - 0 1").unwrap();
+        assert_eq!(
+            sorted(generate::<QuietChecks>(&board)),
+            vec![
+                "c6c7", "e4c3", "e4c5", "e4d2", "e4d6", "e4f2", "e4f6", "e4g3", "e4g5"
+            ]
+        );
+    }
+
+    #[test]
+    fn castling_can_give_check() {
+        let board = ChessBoard::from_str("5k2/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert_eq!(
+            sorted(generate::<QuietChecks>(&board)),
+            vec!["e1g1", "h1f1", "h1h8"]
+        );
+    }
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use board_representation::chess_board::ChessBoard;
+use common::for_each_node;
+use moves_generation::chess_move::move_piece_uci;
+use std::str::FromStr;
+
+fn play(moves: &[&str]) -> ChessBoard {
+    let mut board = ChessBoard::default();
+    moves
+        .iter()
+        .for_each(|uci| move_piece_uci(&mut board, uci).unwrap());
+    board
+}
+
+#[cfg(test)]
+mod zobrist {
+    use super::*;
+
+    #[test]
+    fn incremental_hash_along_perft_trees() {
+        for_each_node(3, |board| {
+            assert_eq!(board.hash(), board.compute_hash(), "{}", board.to_fen());
+        });
+    }
+
+    #[test]
+    fn transpositions_have_the_same_hash() {
+        let board_1 = play(&["g1f3", "g8f6", "b1c3", "b8c6"]);
+        let board_2 = play(&["b1c3", "b8c6", "g1f3", "g8f6"]);
+        assert_eq!(board_1.hash(), board_2.hash());
+        assert_ne!(board_1.hash(), ChessBoard::default().hash());
+    }
+
+    #[test]
+    fn returning_to_a_position_restores_the_hash() {
+        let board = play(&["g1f3", "g8f6", "f3g1", "f6g8"]);
+        assert_eq!(board.hash(), ChessBoard::default().hash());
+    }
+
+    #[test]
+    fn hash_depends_on_side_to_move() {
+        let white_to_move = ChessBoard::from_str("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let black_to_move = ChessBoard::from_str("4k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+        assert_ne!(white_to_move.hash(), black_to_move.hash());
+    }
+
+    #[test]
+    fn hash_depends_on_castling_rights() {
+        let all_rights = play(&["g1f3", "g8f6"]);
+        let lost_rights = play(&["g1f3", "g8f6", "h1g1", "f6g8", "g1h1", "g8f6"]);
+        assert_ne!(all_rights.hash(), lost_rights.hash());
+    }
+
+    #[test]
+    fn hash_depends_on_en_passant_target_square() {
+        let double_push = play(&["e2e4"]);
+        let with_en_passant = double_push;
+        let mut without_en_passant = double_push;
+        without_en_passant.set_en_passant_target_square(None);
+        assert_ne!(with_en_passant.hash(), without_en_passant.hash());
+        assert_eq!(without_en_passant.hash(), without_en_passant.compute_hash());
+    }
+
+    #[test]
+    fn hash_does_not_depend_on_move_counters() {
+        let board_1 = ChessBoard::from_str("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let board_2 = ChessBoard::from_str("4k3/8/8/8/8/8/8/4K3 w - - 17 60").unwrap();
+        assert_eq!(board_1.hash(), board_2.hash());
+    }
+}
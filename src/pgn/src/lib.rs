@@ -0,0 +1,9 @@
+pub mod pgn_error;
+pub mod pgn_game;
+pub mod reader;
+mod tokenizer;
+mod writer;
+
+pub use pgn_error::PgnError;
+pub use pgn_game::{Evaluation, GameResult, PgnGame, PgnMove, Variation};
+pub use reader::PgnReader;
@@ -0,0 +1,99 @@
+use board_representation::fen_error::FenError;
+use moves_generation::san::SanParseError;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+use std::io;
+
+/// Reason why a game could not be read. `line` is the line of the input, starting at 1,
+/// where the offending token starts.
+#[derive(Debug)]
+pub enum PgnError {
+    Io(io::Error),
+    /// A token that is not valid PGN, or not valid at this point of the game.
+    UnexpectedToken {
+        found: String,
+        line: usize,
+    },
+    InvalidTagPair {
+        line: usize,
+    },
+    UnterminatedComment {
+        line: usize,
+    },
+    UnterminatedVariation {
+        line: usize,
+    },
+    /// Variations nested deeper than the reader follows them, 64 levels.
+    VariationTooDeep {
+        line: usize,
+    },
+    InvalidFen {
+        error: FenError,
+        line: usize,
+    },
+    /// `ply` counts the half-moves from the starting position of the game, the first move
+    /// being ply 1.
+    IllegalMove {
+        san: String,
+        ply: usize,
+        error: SanParseError,
+        line: usize,
+    },
+}
+
+impl PgnError {
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            PgnError::Io(_) => None,
+            PgnError::UnexpectedToken { line, .. }
+            | PgnError::InvalidTagPair { line }
+            | PgnError::UnterminatedComment { line }
+            | PgnError::UnterminatedVariation { line }
+            | PgnError::VariationTooDeep { line }
+            | PgnError::InvalidFen { line, .. }
+            | PgnError::IllegalMove { line, .. } => Some(*line),
+        }
+    }
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line() {
+            write!(f, "Invalid PGN at line {}, ", line)?;
+        }
+        match self {
+            PgnError::Io(error) => write!(f, "Could not read PGN: {}", error),
+            PgnError::UnexpectedToken { found, .. } => write!(f, "unexpected '{}'", found),
+            PgnError::InvalidTagPair { .. } => {
+                write!(f, "expected a tag pair such as [Event \"Casual game\"]")
+            }
+            PgnError::UnterminatedComment { .. } => write!(f, "comment is never closed"),
+            PgnError::UnterminatedVariation { .. } => write!(f, "variation is never closed"),
+            PgnError::VariationTooDeep { .. } => write!(f, "variations are nested too deep"),
+            PgnError::InvalidFen { error, .. } => write!(f, "{}", error),
+            PgnError::IllegalMove {
+                san, ply, error, ..
+            } => {
+                write!(f, "move '{}' at ply {}: {}", san, ply, error)
+            }
+        }
+    }
+}
+
+impl Error for PgnError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PgnError::Io(error) => Some(error),
+            PgnError::InvalidFen { error, .. } => Some(error),
+            PgnError::IllegalMove { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PgnError {
+    fn from(error: io::Error) -> Self {
+        PgnError::Io(error)
+    }
+}
@@ -0,0 +1,190 @@
+use board_representation::chess_board::ChessBoard;
+use moves_generation::Move;
+use moves_generation::chess_move::MakeMove;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    /// The game is still going on, was abandoned, or the result is not known.
+    Unknown,
+}
+
+impl GameResult {
+    pub fn from_pgn_token(token: &str) -> Option<GameResult> {
+        match token {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Unknown),
+            _ => None,
+        }
+    }
+
+    pub fn to_pgn_token(self) -> &'static str {
+        match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Unknown => "*",
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_pgn_token())
+    }
+}
+
+/// An engine evaluation from White's point of view, written as a `[%eval ...]` comment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Evaluation {
+    Centipawns(i32),
+    /// Mate in this many moves, negative when Black mates.
+    MateIn(i32),
+}
+
+impl fmt::Display for Evaluation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Evaluation::Centipawns(centipawns) => {
+                let sign = if *centipawns < 0 { "-" } else { "" };
+                let centipawns = centipawns.unsigned_abs();
+                write!(
+                    f,
+                    "[%eval {}{}.{:02}]",
+                    sign,
+                    centipawns / 100,
+                    centipawns % 100
+                )
+            }
+            Evaluation::MateIn(moves) => write!(f, "[%eval #{}]", moves),
+        }
+    }
+}
+
+/// A move of the movetext with its annotations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PgnMove {
+    pub mov: Move,
+    /// Numeric Annotation Glyphs, `$1` or `!` is 1, `$2` or `?` is 2 and so on.
+    pub nags: Vec<u8>,
+    /// The comments following the move, joined by spaces.
+    pub comment: Option<String>,
+    /// Alternatives to this move, played from the position before it.
+    pub variations: Vec<Variation>,
+}
+
+impl PgnMove {
+    pub fn new(mov: Move) -> Self {
+        Self {
+            mov,
+            nags: Vec::new(),
+            comment: None,
+            variations: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Variation {
+    /// The comment preceding the first move.
+    pub comment: Option<String>,
+    pub moves: Vec<PgnMove>,
+}
+
+/// A game read from PGN. The moves are legal, they have been replayed from `starting_board`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PgnGame {
+    /// The tag pairs in the order they appear, e.g. `("White", "Carlsen, Magnus")`.
+    pub tags: Vec<(String, String)>,
+    /// The standard starting position, or the `FEN` tag.
+    pub starting_board: ChessBoard,
+    pub mainline: Variation,
+    pub result: GameResult,
+}
+
+impl PgnGame {
+    /// An empty game from `starting_board`, to be filled with the builder methods.
+    /// The Seven Tag Roster is always written, with `?` for the missing tags.
+    pub fn new(starting_board: ChessBoard) -> Self {
+        Self {
+            tags: Vec::new(),
+            starting_board,
+            mainline: Variation::default(),
+            result: GameResult::Unknown,
+        }
+    }
+
+    /// Sets the tag `name`, replacing its previous value.
+    pub fn with_tag(mut self, name: &str, value: &str) -> Self {
+        self.set_tag(name, value);
+        self
+    }
+
+    /// Appends `moves` to the mainline, they must be legal.
+    pub fn with_moves(mut self, moves: impl IntoIterator<Item = Move>) -> Self {
+        for mov in moves {
+            self.push_move(mov);
+        }
+        self
+    }
+
+    pub fn with_result(mut self, result: GameResult) -> Self {
+        self.result = result;
+        self
+    }
+
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag_name, _)| tag_name == name) {
+            Some((_, tag_value)) => *tag_value = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Appends a legal move to the mainline.
+    pub fn push_move(&mut self, mov: Move) {
+        self.mainline.moves.push(PgnMove::new(mov));
+    }
+
+    /// Appends a legal move to the mainline, commented with the evaluation of the position
+    /// it leads to.
+    pub fn push_evaluated_move(&mut self, mov: Move, evaluation: Evaluation) {
+        let mut pgn_move = PgnMove::new(mov);
+        pgn_move.comment = Some(evaluation.to_string());
+        self.mainline.moves.push(pgn_move);
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag_name, _)| tag_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> + '_ {
+        self.mainline.moves.iter().map(|pgn_move| pgn_move.mov)
+    }
+
+    /// Replays the mainline, yielding each move along with the position it is played in.
+    pub fn positions(&self) -> impl Iterator<Item = (ChessBoard, Move)> + '_ {
+        self.moves().scan(self.starting_board, |board, mov| {
+            let position = *board;
+            board.make_move(mov);
+            Some((position, mov))
+        })
+    }
+
+    /// The position at the end of the mainline.
+    pub fn final_board(&self) -> ChessBoard {
+        let mut board = self.starting_board;
+        for mov in self.moves() {
+            board.make_move(mov);
+        }
+        board
+    }
+}
@@ -0,0 +1,235 @@
+use crate::pgn_error::PgnError;
+use crate::pgn_game::{GameResult, PgnGame, PgnMove, Variation};
+use crate::tokenizer::{Token, Tokenizer};
+use board_representation::chess_board::ChessBoard;
+use moves_generation::Move;
+use moves_generation::chess_move::MakeMove;
+use std::io::BufRead;
+
+/// Nesting levels of variations followed by the reader, each level being read recursively.
+const MAX_VARIATION_DEPTH: usize = 64;
+
+/// Reads the games of a PGN input one after the other.
+///
+/// The input is read line by line, so only the game being parsed is held in memory.
+/// Each game is replayed from its starting position; when a game cannot be read, its error
+/// is returned and the reader moves on to the next game.
+///
+/// ```no_run
+/// use pgn::PgnReader;
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// let file = File::open("games.pgn").unwrap();
+/// for game in PgnReader::new(BufReader::new(file)) {
+///     match game {
+///         Ok(game) => println!("{} moves", game.moves().count()),
+///         Err(error) => eprintln!("{}", error),
+///     }
+/// }
+/// ```
+pub struct PgnReader<R: BufRead> {
+    tokenizer: Tokenizer<R>,
+    peeked: Option<(Token, usize)>,
+    /// Whether the tag pairs of the current game have all been read.
+    in_movetext: bool,
+    finished: bool,
+}
+
+fn append_comment(comment: &mut Option<String>, text: String) {
+    match comment {
+        Some(comment) => {
+            comment.push(' ');
+            comment.push_str(&text);
+        }
+        None => *comment = Some(text),
+    }
+}
+
+fn unexpected(token: &Token, line: usize) -> PgnError {
+    let found = match token {
+        Token::TagPair(name, _) => format!("[{}", name),
+        Token::San(san) => san.clone(),
+        Token::Nag(nag) => format!("${}", nag),
+        Token::Comment(_) => "{".to_string(),
+        Token::VariationStart => "(".to_string(),
+        Token::VariationEnd => ")".to_string(),
+        Token::Result(result) => result.to_string(),
+    };
+    PgnError::UnexpectedToken { found, line }
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            tokenizer: Tokenizer::new(reader),
+            peeked: None,
+            in_movetext: false,
+            finished: false,
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<(Token, usize)>, PgnError> {
+        match self.peeked.take() {
+            Some(token) => Ok(Some(token)),
+            None => self.tokenizer.next_token(),
+        }
+    }
+
+    pub fn read_game(&mut self) -> Result<Option<PgnGame>, PgnError> {
+        self.in_movetext = false;
+        let mut tags = Vec::new();
+        let mut fen_line = 0;
+        loop {
+            match self.next_token()? {
+                None if tags.is_empty() => return Ok(None),
+                Some((Token::TagPair(name, value), line)) => {
+                    if name == "FEN" {
+                        fen_line = line;
+                    }
+                    tags.push((name, value));
+                }
+                token => {
+                    self.peeked = token;
+                    break;
+                }
+            }
+        }
+        self.in_movetext = true;
+
+        let starting_board = match tags.iter().find(|(name, _)| name == "FEN") {
+            Some((_, fen)) => {
+                let chess960 = tags.iter().any(|(name, value)| {
+                    name == "Variant" && value.eq_ignore_ascii_case("chess960")
+                });
+                let board = if chess960 {
+                    ChessBoard::from_chess960_fen_validated(fen)
+                } else {
+                    ChessBoard::from_fen_validated(fen)
+                };
+                board.map_err(|error| PgnError::InvalidFen {
+                    error,
+                    line: fen_line,
+                })?
+            }
+            None => ChessBoard::default(),
+        };
+        let (mainline, result) = self.read_variation(starting_board, 0, 0)?;
+        Ok(Some(PgnGame {
+            tags,
+            starting_board,
+            mainline,
+            result: result.unwrap_or(GameResult::Unknown),
+        }))
+    }
+
+    /// Reads the moves played from `board`, after `ply` half-moves, up to the end of the
+    /// variation or of the game. Only the mainline, at depth 0, ends with a result.
+    fn read_variation(
+        &mut self,
+        mut board: ChessBoard,
+        mut ply: usize,
+        depth: usize,
+    ) -> Result<(Variation, Option<GameResult>), PgnError> {
+        let mut variation = Variation::default();
+        let mut previous_board = board;
+        loop {
+            let Some((token, line)) = self.next_token()? else {
+                if depth > 0 {
+                    return Err(PgnError::UnterminatedVariation {
+                        line: self.tokenizer.line_number(),
+                    });
+                }
+                return Ok((variation, None));
+            };
+            match token {
+                Token::San(san) => {
+                    let mov =
+                        Move::from_san(&board, &san).map_err(|error| PgnError::IllegalMove {
+                            san,
+                            ply: ply + 1,
+                            error,
+                            line,
+                        })?;
+                    previous_board = board;
+                    board.make_move(mov);
+                    ply += 1;
+                    variation.moves.push(PgnMove::new(mov));
+                }
+                Token::Nag(nag) => match variation.moves.last_mut() {
+                    Some(pgn_move) => pgn_move.nags.push(nag),
+                    None => return Err(unexpected(&token, line)),
+                },
+                Token::Comment(comment) => match variation.moves.last_mut() {
+                    Some(pgn_move) => append_comment(&mut pgn_move.comment, comment),
+                    None => append_comment(&mut variation.comment, comment),
+                },
+                Token::VariationStart => {
+                    if variation.moves.is_empty() {
+                        return Err(unexpected(&token, line));
+                    }
+                    if depth == MAX_VARIATION_DEPTH {
+                        return Err(PgnError::VariationTooDeep { line });
+                    }
+                    let (alternative, _) =
+                        self.read_variation(previous_board, ply - 1, depth + 1)?;
+                    if let Some(pgn_move) = variation.moves.last_mut() {
+                        pgn_move.variations.push(alternative);
+                    }
+                }
+                Token::VariationEnd if depth > 0 => return Ok((variation, None)),
+                Token::Result(result) if depth == 0 => return Ok((variation, Some(result))),
+                // The next game starts, this one has no result.
+                Token::TagPair(..) => {
+                    self.peeked = Some((token, line));
+                    if depth > 0 {
+                        return Err(PgnError::UnterminatedVariation { line });
+                    }
+                    return Ok((variation, None));
+                }
+                Token::VariationEnd | Token::Result(_) => return Err(unexpected(&token, line)),
+            }
+        }
+    }
+
+    /// Skips the rest of a game that could not be read.
+    fn skip_game(&mut self) {
+        loop {
+            match self.next_token() {
+                Ok(None) | Ok(Some((Token::Result(_), _))) => return,
+                Ok(Some((Token::TagPair(name, value), line))) if self.in_movetext => {
+                    self.peeked = Some((Token::TagPair(name, value), line));
+                    return;
+                }
+                Ok(Some((Token::TagPair(..), _))) => {}
+                Ok(Some(_)) => self.in_movetext = true,
+                Err(PgnError::Io(_)) => {
+                    self.finished = true;
+                    return;
+                }
+                Err(_) => {}
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.read_game() {
+            Ok(game) => game.map(Ok),
+            Err(PgnError::Io(error)) => {
+                self.finished = true;
+                Some(Err(PgnError::Io(error)))
+            }
+            Err(error) => {
+                self.skip_game();
+                Some(Err(error))
+            }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use crate::pgn_error::PgnError;
+use crate::pgn_game::GameResult;
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Token {
+    TagPair(String, String),
+    /// A move in SAN, without move number nor annotation suffix.
+    San(String),
+    Nag(u8),
+    Comment(String),
+    VariationStart,
+    VariationEnd,
+    Result(GameResult),
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "_+#=:-/!?.".contains(c)
+}
+
+/// Suffix annotations and the Numeric Annotation Glyphs they stand for.
+fn suffix_nag(suffix: &str) -> Option<u8> {
+    match suffix {
+        "!" => Some(1),
+        "?" => Some(2),
+        "!!" => Some(3),
+        "??" => Some(4),
+        "!?" => Some(5),
+        "?!" => Some(6),
+        _ => None,
+    }
+}
+
+/// Splits PGN into tokens, reading the input one line at a time.
+pub(crate) struct Tokenizer<R: BufRead> {
+    reader: R,
+    bytes: Vec<u8>,
+    line: String,
+    /// Byte offset of the next character in `line`.
+    position: usize,
+    line_number: usize,
+    pending: VecDeque<(Token, usize)>,
+}
+
+impl<R: BufRead> Tokenizer<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            bytes: Vec::new(),
+            line: String::new(),
+            position: 0,
+            line_number: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Loads the next line, returns false at the end of the input.
+    fn read_line(&mut self) -> Result<bool, PgnError> {
+        loop {
+            self.bytes.clear();
+            if self.reader.read_until(b'\n', &mut self.bytes)? == 0 {
+                self.line.clear();
+                self.position = 0;
+                return Ok(false);
+            }
+            self.line_number += 1;
+            self.position = 0;
+            self.line.clear();
+            match std::str::from_utf8(&self.bytes) {
+                Ok(line) => self.line.push_str(line),
+                // Older PGN files are often encoded in Latin-1.
+                Err(_) => self
+                    .line
+                    .extend(self.bytes.iter().map(|byte| char::from(*byte))),
+            }
+            // A '%' in the first column escapes the whole line.
+            if !self.line.starts_with('%') {
+                return Ok(true);
+            }
+            self.line.clear();
+        }
+    }
+
+    fn peek_char(&mut self) -> Result<Option<char>, PgnError> {
+        loop {
+            if let Some(c) = self.line[self.position..].chars().next() {
+                return Ok(Some(c));
+            }
+            if !self.read_line()? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Returns the next character of the current line, without moving to the next line.
+    fn next_char_in_line(&mut self) -> Option<char> {
+        let c = self.line[self.position..].chars().next()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    pub(crate) fn next_token(&mut self) -> Result<Option<(Token, usize)>, PgnError> {
+        if let Some(token) = self.pending.pop_front() {
+            return Ok(Some(token));
+        }
+        loop {
+            let Some(c) = self.peek_char()? else {
+                return Ok(None);
+            };
+            let line = self.line_number;
+            self.position += c.len_utf8();
+            let token = match c {
+                c if c.is_whitespace() => continue,
+                ';' => {
+                    let comment = self.line[self.position..].trim().to_string();
+                    self.position = self.line.len();
+                    Token::Comment(comment)
+                }
+                '{' => Token::Comment(self.comment(line)?),
+                '[' => self.tag_pair(line)?,
+                '(' => Token::VariationStart,
+                ')' => Token::VariationEnd,
+                '*' => Token::Result(GameResult::Unknown),
+                '$' => {
+                    let digits = self.take_while(|c| c.is_ascii_digit());
+                    let nag = digits.parse().map_err(|_| PgnError::UnexpectedToken {
+                        found: format!("${}", digits),
+                        line,
+                    })?;
+                    Token::Nag(nag)
+                }
+                c if is_symbol_char(c) => {
+                    let symbol = format!("{}{}", c, self.take_while(is_symbol_char));
+                    self.symbol(symbol, line)?;
+                    match self.pending.pop_front() {
+                        Some((token, _)) => token,
+                        None => continue,
+                    }
+                }
+                c => {
+                    return Err(PgnError::UnexpectedToken {
+                        found: c.to_string(),
+                        line,
+                    });
+                }
+            };
+            return Ok(Some((token, line)));
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let rest = &self.line[self.position..];
+        let length = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.position += length;
+        rest[..length].to_string()
+    }
+
+    /// Reads a brace comment, which may span several lines, collapsing whitespace.
+    fn comment(&mut self, line: usize) -> Result<String, PgnError> {
+        let mut comment = String::new();
+        loop {
+            let rest = &self.line[self.position..];
+            if let Some(end) = rest.find('}') {
+                comment.push_str(&rest[..end]);
+                self.position += end + 1;
+                return Ok(comment.split_whitespace().collect::<Vec<_>>().join(" "));
+            }
+            comment.push_str(rest);
+            comment.push(' ');
+            if !self.read_line()? {
+                return Err(PgnError::UnterminatedComment { line });
+            }
+        }
+    }
+
+    /// Reads `Name "value"]`, the opening bracket being already consumed.
+    fn tag_pair(&mut self, line: usize) -> Result<Token, PgnError> {
+        let invalid = PgnError::InvalidTagPair { line };
+        self.take_while(char::is_whitespace);
+        let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        self.take_while(char::is_whitespace);
+        if name.is_empty() || self.next_char_in_line() != Some('"') {
+            return Err(invalid);
+        }
+        let mut value = String::new();
+        loop {
+            match self.next_char_in_line() {
+                Some('"') => break,
+                Some('\\') => match self.next_char_in_line() {
+                    Some(c @ ('"' | '\\')) => value.push(c),
+                    _ => return Err(invalid),
+                },
+                Some(c) => value.push(c),
+                None => return Err(invalid),
+            }
+        }
+        self.take_while(char::is_whitespace);
+        if self.next_char_in_line() != Some(']') {
+            return Err(invalid);
+        }
+        Ok(Token::TagPair(name, value))
+    }
+
+    /// Queues the tokens of a symbol: a result, or a move possibly preceded by its number
+    /// and followed by an annotation suffix such as `!?`.
+    fn symbol(&mut self, symbol: String, line: usize) -> Result<(), PgnError> {
+        if let Some(result) = GameResult::from_pgn_token(&symbol) {
+            self.pending.push_back((Token::Result(result), line));
+            return Ok(());
+        }
+        // Move numbers such as "12.", "12..." or "12.Nf3". "0-0" is castling, not a number.
+        let digits = symbol.len()
+            - symbol
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+        let san = match symbol[digits..].strip_prefix('.') {
+            Some(rest) => rest.trim_start_matches('.'),
+            None if digits == symbol.len() => "",
+            None => &symbol,
+        };
+        if san.is_empty() || san == "e.p." || san == "ep" {
+            return Ok(());
+        }
+
+        let annotation_start = san
+            .rfind(|c| c != '!' && c != '?')
+            .map_or(0, |index| index + 1);
+        let (san, suffix) = san.split_at(annotation_start);
+        if !san.is_empty() {
+            self.pending.push_back((Token::San(san.to_string()), line));
+        }
+        if !suffix.is_empty() {
+            let nag = suffix_nag(suffix).ok_or(PgnError::UnexpectedToken {
+                found: suffix.to_string(),
+                line,
+            })?;
+            self.pending.push_back((Token::Nag(nag), line));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use crate::pgn_game::{PgnGame, Variation};
+use board_representation::chess_board::ChessBoard;
+use board_representation::chess_piece::Color;
+use moves_generation::chess_move::MakeMove;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// The tags every PGN game starts with, in this order, and their value when unknown.
+const SEVEN_TAG_ROSTER: [(&str, &str); 7] = [
+    ("Event", "?"),
+    ("Site", "?"),
+    ("Date", "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+    ("Result", "*"),
+];
+
+const MAX_LINE_LENGTH: usize = 80;
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Comments are split into words so that they can be wrapped like the rest of the movetext.
+fn push_comment(words: &mut Vec<String>, comment: &str) {
+    let mut comment_words = comment
+        .split_whitespace()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if comment_words.is_empty() {
+        comment_words.push(String::new());
+    }
+    comment_words[0].insert(0, '{');
+    if let Some(last) = comment_words.last_mut() {
+        last.push('}');
+    }
+    words.extend(comment_words);
+}
+
+/// Appends the words of the movetext of `variation`, played from `board`.
+fn push_variation(words: &mut Vec<String>, mut board: ChessBoard, variation: &Variation) {
+    if let Some(comment) = &variation.comment {
+        push_comment(words, comment);
+    }
+    // Black moves are numbered at the start of a line and after an interruption.
+    let mut needs_move_number = true;
+    for pgn_move in &variation.moves {
+        match board.current_turn() {
+            Color::White => words.push(format!("{}.", board.fullmove_number())),
+            Color::Black if needs_move_number => {
+                words.push(format!("{}...", board.fullmove_number()))
+            }
+            Color::Black => {}
+        }
+        words.push(pgn_move.mov.to_san(&board));
+        words.extend(pgn_move.nags.iter().map(|nag| format!("${}", nag)));
+        if let Some(comment) = &pgn_move.comment {
+            push_comment(words, comment);
+        }
+        for alternative in &pgn_move.variations {
+            let mut alternative_words = Vec::new();
+            push_variation(&mut alternative_words, board, alternative);
+            match alternative_words.first_mut() {
+                Some(first) => first.insert(0, '('),
+                None => alternative_words.push("(".to_string()),
+            }
+            if let Some(last) = alternative_words.last_mut() {
+                last.push(')');
+            }
+            words.extend(alternative_words);
+        }
+        needs_move_number = pgn_move.comment.is_some() || !pgn_move.variations.is_empty();
+        board.make_move(pgn_move.mov);
+    }
+}
+
+/// Writes the game in export format: the Seven Tag Roster first, `SetUp` and `FEN` when the
+/// game does not start from the standard position, the other tags, then the movetext in SAN
+/// wrapped at 80 columns.
+impl fmt::Display for PgnGame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (name, unknown) in SEVEN_TAG_ROSTER {
+            let value = match name {
+                "Result" => self.result.to_pgn_token(),
+                _ => self.tag(name).unwrap_or(unknown),
+            };
+            writeln!(f, "[{} \"{}\"]", name, escape(value))?;
+        }
+        if self.starting_board != ChessBoard::default() {
+            writeln!(f, "[SetUp \"1\"]")?;
+            writeln!(f, "[FEN \"{}\"]", self.starting_board.to_fen())?;
+        }
+        let is_written = |name: &str| {
+            SEVEN_TAG_ROSTER
+                .iter()
+                .any(|(roster_name, _)| *roster_name == name)
+                || name == "SetUp"
+                || name == "FEN"
+        };
+        for (name, value) in self.tags.iter().filter(|(name, _)| !is_written(name)) {
+            writeln!(f, "[{} \"{}\"]", name, escape(value))?;
+        }
+        writeln!(f)?;
+
+        let mut words = Vec::new();
+        push_variation(&mut words, self.starting_board, &self.mainline);
+        words.push(self.result.to_pgn_token().to_string());
+
+        let mut line_length = 0;
+        for word in words {
+            if line_length > 0 && line_length + 1 + word.len() > MAX_LINE_LENGTH {
+                writeln!(f)?;
+                line_length = 0;
+            }
+            if line_length > 0 {
+                write!(f, " ")?;
+                line_length += 1;
+            }
+            write!(f, "{}", word)?;
+            line_length += word.len();
+        }
+        writeln!(f)
+    }
+}
@@ -0,0 +1,306 @@
+use board_representation::chess_board::ChessBoard;
+use board_representation::fen_error::FenError;
+use moves_generation::san::SanParseError;
+use pgn::{GameResult, PgnError, PgnGame, PgnReader};
+use std::io::{BufReader, Cursor, Read};
+
+const IMMORTAL_GAME: &str = r#"[Event "Casual game"]
+[Site "London ENG"]
+[Date "1851.06.21"]
+[Round "?"]
+[White "Anderssen, Adolf"]
+[Black "Kieseritzky, Lionel"]
+[Result "1-0"]
+
+1. e4 e5 2. f4 exf4 3. Bc4 Qh4+ 4. Kf1 b5 5. Bxb5 Nf6 6. Nf3 Qh6 7. d3 Nh5 8. Nh4
+Qg5 9. Nf5 c6 10. g4 Nf6 11. Rg1 cxb5 12. h4 Qg6 13. h5 Qg5 14. Qf3 Ng8 15. Bxf4
+Qf6 16. Nc3 Bc5 17. Nd5 Qxb2 18. Bd6 Bxg1 19. e5 Qxa1+ 20. Ke2 Na6 21. Nxg7+ Kd8
+22. Qf6+ Nxf6 23. Be7# 1-0
+"#;
+
+const ANNOTATED_GAME: &str = r#"[Event "Annotated"]
+[White "A"]
+[Black "B"]
+[Result "1/2-1/2"]
+
+{The Ruy Lopez.} 1. e4 e5 2. Nf3 $1 Nc6 (2... d6 {Philidor} 3. d4 (3. Bc4) 3... Nf6)
+3. Bb5!? a6 ; the Morphy defence
+4. Ba4 {A long comment
+  spanning two lines} Nf6 5. O-O 1/2-1/2
+"#;
+
+fn read_all(pgn: &str) -> Vec<Result<PgnGame, PgnError>> {
+    PgnReader::new(Cursor::new(pgn)).collect()
+}
+
+fn read_one(pgn: &str) -> PgnGame {
+    let mut games = read_all(pgn);
+    assert_eq!(games.len(), 1);
+    games.remove(0).unwrap()
+}
+
+fn uci(moves: impl Iterator<Item = moves_generation::Move>) -> Vec<String> {
+    moves.map(|mov| mov.to_uci_string()).collect()
+}
+
+/// Produces the same game over and over, without end.
+struct EndlessGames {
+    game: &'static [u8],
+    position: usize,
+}
+
+impl Read for EndlessGames {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let length = buf.len().min(self.game.len() - self.position);
+        buf[..length].copy_from_slice(&self.game[self.position..self.position + length]);
+        self.position = (self.position + length) % self.game.len();
+        Ok(length)
+    }
+}
+
+#[cfg(test)]
+mod reader {
+    use super::*;
+
+    #[test]
+    fn reads_tags_and_replays_the_mainline() {
+        let game = read_one(IMMORTAL_GAME);
+        assert_eq!(game.tag("White"), Some("Anderssen, Adolf"));
+        assert_eq!(game.tag("Round"), Some("?"));
+        assert_eq!(game.tag("ECO"), None);
+        assert_eq!(game.tags.len(), 7);
+        assert_eq!(game.result, GameResult::WhiteWins);
+        assert_eq!(game.moves().count(), 45);
+        assert_eq!(
+            game.final_board().to_fen(),
+            "r1bk3r/p2pBpNp/n4n2/1p1NP2P/6P1/3P4/P1P1K3/q5b1 b - - 1 23"
+        );
+    }
+
+    #[test]
+    fn positions_along_the_mainline() {
+        let game = read_one(IMMORTAL_GAME);
+        let positions = game.positions().collect::<Vec<_>>();
+        assert_eq!(positions.len(), 45);
+        assert_eq!(positions[0].0, ChessBoard::default());
+        assert_eq!(positions[0].1.to_uci_string(), "e2e4");
+        assert_eq!(
+            positions[2].0.to_fen(),
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2"
+        );
+        for (board, mov) in positions {
+            assert!(mov.to_san(&board).len() >= 2);
+        }
+    }
+
+    #[test]
+    fn comments_nags_and_variations() {
+        let game = read_one(ANNOTATED_GAME);
+        assert_eq!(game.result, GameResult::Draw);
+        assert_eq!(game.mainline.comment.as_deref(), Some("The Ruy Lopez."));
+        let moves = &game.mainline.moves;
+        assert_eq!(
+            uci(game.moves()),
+            vec![
+                "e2e4", "e7e5", "g1f3", "b8c6", "f1b5", "a7a6", "b5a4", "g8f6", "e1g1"
+            ]
+        );
+
+        assert_eq!(moves[2].nags, vec![1]);
+        assert_eq!(moves[4].nags, vec![5]);
+        assert_eq!(moves[5].comment.as_deref(), Some("the Morphy defence"));
+        assert_eq!(
+            moves[6].comment.as_deref(),
+            Some("A long comment spanning two lines")
+        );
+
+        assert_eq!(moves[3].variations.len(), 1);
+        let variation = &moves[3].variations[0];
+        assert_eq!(
+            uci(variation.moves.iter().map(|m| m.mov)),
+            ["d7d6", "d2d4", "g8f6"]
+        );
+        assert_eq!(variation.moves[0].comment.as_deref(), Some("Philidor"));
+        let nested = &variation.moves[1].variations[0];
+        assert_eq!(uci(nested.moves.iter().map(|m| m.mov)), ["f1c4"]);
+    }
+
+    #[test]
+    fn several_games() {
+        let pgn = format!("{}\n{}\n{}", IMMORTAL_GAME, ANNOTATED_GAME, IMMORTAL_GAME);
+        let games = read_all(&pgn);
+        assert_eq!(games.len(), 3);
+        assert!(games.iter().all(|game| game.is_ok()));
+        assert_eq!(games[1].as_ref().unwrap().tag("Event"), Some("Annotated"));
+    }
+
+    #[test]
+    fn games_without_tags_or_result() {
+        let games = read_all(
+            "1. e4 e5 1-0\n1. d4 *\n\n[Event \"No result\"]\n1. c4\n[Event \"Next\"]\n1. Nf3",
+        );
+        let move_counts = games
+            .iter()
+            .map(|game| game.as_ref().unwrap().moves().count())
+            .collect::<Vec<_>>();
+        assert_eq!(move_counts, vec![2, 1, 1, 1]);
+        assert_eq!(games[2].as_ref().unwrap().result, GameResult::Unknown);
+        assert_eq!(games[3].as_ref().unwrap().tag("Event"), Some("Next"));
+    }
+
+    #[test]
+    fn starting_position_from_fen_tag() {
+        let game = read_one(
+            "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 40\"]\n\n40... Kd7 41. e4 *",
+        );
+        assert_eq!(
+            game.starting_board.to_fen(),
+            "4k3/8/8/8/8/8/4P3/4K3 b - - 0 40"
+        );
+        assert_eq!(uci(game.moves()), vec!["e8d7", "e2e4"]);
+    }
+
+    #[test]
+    fn sloppy_movetext() {
+        let pgn = "% exported by some tool\n\
+                   1.e4 e5 2.Nf3 Nc6 3.Bc4 Bc5 4.0-0 Nf6?? 5.Ng5 d5 6.exd5 Nxd5 7.Nxf7!! *";
+        let game = read_one(pgn);
+        assert_eq!(game.moves().count(), 13);
+        let moves = &game.mainline.moves;
+        assert_eq!(moves[6].mov.to_uci_string(), "e1g1");
+        assert_eq!(moves[7].nags, vec![4]);
+        assert_eq!(moves[12].nags, vec![3]);
+    }
+
+    #[test]
+    fn latin_1_tags() {
+        let mut pgn = b"[White \"M".to_vec();
+        pgn.push(0xfc);
+        pgn.extend_from_slice(b"ller\"]\n\n1. e4 *\n");
+        let games = PgnReader::new(Cursor::new(pgn)).collect::<Vec<_>>();
+        assert_eq!(games[0].as_ref().unwrap().tag("White"), Some("Müller"));
+    }
+
+    #[test]
+    fn illegal_move_reports_its_ply() {
+        let pgn = format!(
+            "[Event \"Broken\"]\n\n1. e4 e5 2. Nf3 Nc6\n3. Bb5 Nf3 4. O-O *\n\n{}",
+            IMMORTAL_GAME
+        );
+        let games = read_all(&pgn);
+        assert_eq!(games.len(), 2);
+        match &games[0] {
+            Err(PgnError::IllegalMove {
+                san,
+                ply,
+                error,
+                line,
+            }) => {
+                assert_eq!(san, "Nf3");
+                assert_eq!(*ply, 6);
+                assert_eq!(*line, 4);
+                assert_eq!(
+                    *error,
+                    SanParseError::IllegalMove {
+                        found: "Nf3".to_string()
+                    }
+                );
+            }
+            other => panic!("Expected an illegal move, found {:?}", other),
+        }
+        // The reader recovers and reads the next game.
+        assert_eq!(games[1].as_ref().unwrap().moves().count(), 45);
+    }
+
+    #[test]
+    fn illegal_move_in_a_variation() {
+        let games = read_all("1. e4 e5 (1... d5 2. Ke2 Kd6) 2. Nf3 *");
+        assert!(matches!(
+            games[0],
+            Err(PgnError::IllegalMove { ply: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_pgn() {
+        let errors = [
+            ("[Event \"Unclosed]\n1. e4 *", "InvalidTagPair"),
+            ("1. e4 {never closed", "UnterminatedComment"),
+            ("1. e4 (1. d4 *", "UnexpectedToken"),
+            ("1. e4 (1. d4", "UnterminatedVariation"),
+            ("1. e4 ) *", "UnexpectedToken"),
+            ("( 1. e4 ) *", "UnexpectedToken"),
+            ("[FEN \"8/8/8\"]\n1. e4 *", "InvalidFen"),
+        ];
+        for (pgn, expected) in errors {
+            let games = read_all(pgn);
+            let error = games[0].as_ref().unwrap_err();
+            assert!(
+                format!("{:?}", error).starts_with(expected),
+                "{}: {:?}",
+                pgn,
+                error
+            );
+            assert_eq!(error.line(), Some(1), "{}", pgn);
+        }
+    }
+
+    #[test]
+    fn deeply_nested_variations() {
+        let nested = |depth| {
+            format!(
+                "1. e4 {}{} *\n\n1. d4 *\n",
+                "(1. d4 ".repeat(depth),
+                ")".repeat(depth)
+            )
+        };
+        let games = read_all(&nested(64));
+        let mut variations = &games[0].as_ref().unwrap().mainline;
+        for _ in 0..64 {
+            variations = &variations.moves[0].variations[0];
+        }
+        assert!(variations.moves[0].variations.is_empty());
+
+        // Too deep to be read recursively, the next game is read all the same.
+        let games = read_all(&nested(100_000));
+        assert!(matches!(
+            games[0],
+            Err(PgnError::VariationTooDeep { line: 1 })
+        ));
+        assert_eq!(uci(games[1].as_ref().unwrap().moves()), ["d2d4"]);
+    }
+
+    #[test]
+    fn impossible_fen_tag() {
+        let games = read_all("[FEN \"8/8/8/8/8/8/8/8 w - - 0 1\"]\n\n1. e4 *\n\n1. d4 *\n");
+        assert!(matches!(
+            games[0],
+            Err(PgnError::InvalidFen {
+                error: FenError::InvalidPosition { .. },
+                line: 1
+            })
+        ));
+        assert_eq!(uci(games[1].as_ref().unwrap().moves()), ["d2d4"]);
+
+        // KQkq castles from the e-file only, unless the game is Chess960.
+        let fen = "[FEN \"r4k1r/8/8/8/8/8/8/R4K1R w KQkq - 0 1\"]\n\n1. O-O *\n";
+        assert!(matches!(read_all(fen)[0], Err(PgnError::InvalidFen { .. })));
+        let game = read_one(&format!("[Variant \"Chess960\"]\n{}", fen));
+        assert_eq!(uci(game.moves()), ["f1g1"]);
+    }
+
+    #[test]
+    fn streams_the_input() {
+        // Reading the first games of an endless input must not try to load all of it.
+        let input = EndlessGames {
+            game: IMMORTAL_GAME.as_bytes(),
+            position: 0,
+        };
+        let games = PgnReader::new(BufReader::new(input)).take(1000);
+        assert!(
+            games
+                .map(|game| game.unwrap())
+                .all(|game| game.moves().count() == 45)
+        );
+    }
+}
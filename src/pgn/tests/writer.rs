@@ -0,0 +1,151 @@
+use board_representation::chess_board::ChessBoard;
+use moves_generation::Move;
+use moves_generation::chess_move::MakeMove;
+use pgn::{Evaluation, GameResult, PgnGame, PgnReader};
+use std::io::Cursor;
+use std::str::FromStr;
+
+const ANNOTATED_GAME: &str = r#"[Event "Annotated"]
+[White "A"]
+[Black "B"]
+[Result "1/2-1/2"]
+
+{The Ruy Lopez.} 1. e4 e5 2. Nf3 $1 Nc6 (2... d6 {Philidor} 3. d4 (3. Bc4) 3... Nf6)
+3. Bb5!? a6 ; the Morphy defence
+4. Ba4 {A long comment
+  spanning two lines} Nf6 5. O-O 1/2-1/2
+"#;
+
+fn parse(pgn: &str) -> PgnGame {
+    let mut games = PgnReader::new(Cursor::new(pgn)).collect::<Vec<_>>();
+    assert_eq!(games.len(), 1, "{}", pgn);
+    games.remove(0).unwrap()
+}
+
+/// Plays the UCI moves from `board`, returning them as moves.
+fn moves(board: &ChessBoard, uci_moves: &[&str]) -> Vec<Move> {
+    let mut board = *board;
+    uci_moves
+        .iter()
+        .map(|uci| {
+            let mov = Move::from_uci_string(uci, &board).unwrap();
+            board.make_move(mov);
+            mov
+        })
+        .collect()
+}
+
+fn assert_round_trip(game: &PgnGame) {
+    let pgn = game.to_string();
+    let parsed = parse(&pgn);
+    assert_eq!(parsed.mainline, game.mainline);
+    assert_eq!(parsed.starting_board, game.starting_board);
+    assert_eq!(parsed.result, game.result);
+    assert_eq!(parsed.to_string(), pgn);
+}
+
+#[cfg(test)]
+mod writer {
+    use super::*;
+
+    #[test]
+    fn seven_tag_roster() {
+        let board = ChessBoard::default();
+        let game = PgnGame::new(board)
+            .with_tag("White", "Scamorza")
+            .with_tag("TimeControl", "40/7200")
+            .with_tag("Event", "Self-play")
+            .with_moves(moves(&board, &["e2e4", "e7e5", "g1f3"]))
+            .with_result(GameResult::WhiteWins);
+        assert_eq!(
+            game.to_string(),
+            "[Event \"Self-play\"]\n\
+             [Site \"?\"]\n\
+             [Date \"????.??.??\"]\n\
+             [Round \"?\"]\n\
+             [White \"Scamorza\"]\n\
+             [Black \"?\"]\n\
+             [Result \"1-0\"]\n\
+             [TimeControl \"40/7200\"]\n\
+             \n\
+             1. e4 e5 2. Nf3 1-0\n"
+        );
+    }
+
+    #[test]
+    fn set_tag_replaces_the_value() {
+        let mut game = PgnGame::new(ChessBoard::default()).with_tag("Round", "1");
+        game.set_tag("Round", "2");
+        assert_eq!(game.tags, vec![("Round".to_string(), "2".to_string())]);
+    }
+
+    #[test]
+    fn escapes_tag_values() {
+        let game = PgnGame::new(ChessBoard::default()).with_tag("Event", r#"The "Big" \ One"#);
+        assert!(
+            game.to_string()
+                .starts_with(r#"[Event "The \"Big\" \\ One"]"#)
+        );
+        assert_eq!(
+            parse(&game.to_string()).tag("Event"),
+            Some(r#"The "Big" \ One"#)
+        );
+    }
+
+    #[test]
+    fn non_standard_starting_position() {
+        let board = ChessBoard::from_str("4k3/8/8/8/8/8/4P3/4K3 b - - 0 40").unwrap();
+        let game = PgnGame::new(board).with_moves(moves(&board, &["e8d7", "e2e4", "d7d6"]));
+        let pgn = game.to_string();
+        assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 40\"]\n"));
+        assert!(pgn.ends_with("\n40... Kd7 41. e4 Kd6 *\n"));
+        assert_round_trip(&game);
+    }
+
+    #[test]
+    fn evaluation_comments() {
+        let board = ChessBoard::default();
+        let mut game = PgnGame::new(board);
+        let played = moves(&board, &["f2f3", "e7e5", "g2g4", "d8h4"]);
+        game.push_evaluated_move(played[0], Evaluation::Centipawns(-35));
+        game.push_evaluated_move(played[1], Evaluation::Centipawns(5));
+        game.push_evaluated_move(played[2], Evaluation::MateIn(-1));
+        game.push_move(played[3]);
+        let game = game.with_result(GameResult::BlackWins);
+        assert!(game.to_string().ends_with(
+            "\n1. f3 {[%eval -0.35]} 1... e5 {[%eval 0.05]} 2. g4 {[%eval #-1]} 2... Qh4# 0-1\n"
+        ));
+        assert_round_trip(&game);
+    }
+
+    #[test]
+    fn annotations_and_variations() {
+        let game = parse(ANNOTATED_GAME);
+        let pgn = game.to_string();
+        assert!(pgn.ends_with(
+            "\n{The Ruy Lopez.} 1. e4 e5 2. Nf3 $1 Nc6 (2... d6 {Philidor} 3. d4 (3. Bc4) 3...\n\
+             Nf6) 3. Bb5 $5 a6 {the Morphy defence} 4. Ba4 {A long comment spanning two\n\
+             lines} 4... Nf6 5. O-O 1/2-1/2\n"
+        ));
+        assert_round_trip(&game);
+    }
+
+    #[test]
+    fn wraps_at_80_columns() {
+        let board = ChessBoard::default();
+        let mut game = PgnGame::new(board);
+        let mut position = board;
+        // Knights dancing back and forth.
+        for uci in ["g1f3", "g8f6", "f3g1", "f6g8"].iter().cycle().take(80) {
+            let mov = Move::from_uci_string(uci, &position).unwrap();
+            position.make_move(mov);
+            game.push_evaluated_move(mov, Evaluation::Centipawns(12));
+        }
+        let pgn = game.to_string();
+        let movetext = pgn.split("\n\n").nth(1).unwrap();
+        assert!(movetext.lines().count() > 10);
+        assert!(movetext.lines().all(|line| line.len() <= 80));
+        assert!(movetext.lines().any(|line| line.len() > 70));
+        assert_round_trip(&game);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod pruning;
+pub mod searcher;
@@ -0,0 +1,91 @@
+use crate::searcher::Score;
+use board_representation::chess_board::ChessBoard;
+use board_representation::chess_piece::Color;
+
+/// The selective parts of the search, which skip or shorten the moves that are unlikely to
+/// matter. All of them are on by default, each one can be turned off on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pruning {
+    /// Lets the opponent move twice: a position still above beta is cut.
+    pub null_move: bool,
+    /// Searches the late quiet moves less deep, and again at full depth if they beat alpha.
+    pub late_move_reductions: bool,
+    /// Cuts the nodes near the leaves whose static evaluation is well above beta.
+    pub reverse_futility: bool,
+    /// Skips the quiet moves near the leaves that cannot bring the evaluation up to alpha.
+    pub futility: bool,
+    /// Drops into the quiescence search near the leaves when the evaluation is far below alpha.
+    pub razoring: bool,
+    /// Skips the quiet moves near the leaves once enough of them have been searched.
+    pub late_move_pruning: bool,
+}
+
+impl Pruning {
+    pub const ALL: Pruning = Pruning {
+        null_move: true,
+        late_move_reductions: true,
+        reverse_futility: true,
+        futility: true,
+        razoring: true,
+        late_move_pruning: true,
+    };
+
+    pub const NONE: Pruning = Pruning {
+        null_move: false,
+        late_move_reductions: false,
+        reverse_futility: false,
+        futility: false,
+        razoring: false,
+        late_move_pruning: false,
+    };
+}
+
+impl Default for Pruning {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+pub(crate) const NULL_MOVE_MIN_DEPTH: u8 = 3;
+pub(crate) const REVERSE_FUTILITY_MAX_DEPTH: u8 = 6;
+pub(crate) const RAZORING_MAX_DEPTH: u8 = 2;
+pub(crate) const FUTILITY_MAX_DEPTH: u8 = 3;
+pub(crate) const LATE_MOVE_PRUNING_MAX_DEPTH: u8 = 3;
+pub(crate) const LATE_MOVE_REDUCTIONS_MIN_DEPTH: u8 = 3;
+/// The first moves of a node, the best according to the move ordering, are never reduced.
+pub(crate) const LATE_MOVE_REDUCTIONS_MIN_MOVES: usize = 3;
+
+/// How much shallower than a normal move the null move is searched.
+pub(crate) fn null_move_reduction(depth: u8) -> u8 {
+    2 + depth / 4
+}
+
+pub(crate) fn reverse_futility_margin(depth: u8) -> Score {
+    120 * depth as Score
+}
+
+pub(crate) fn razoring_margin(depth: u8) -> Score {
+    300 + 200 * depth as Score
+}
+
+pub(crate) fn futility_margin(depth: u8) -> Score {
+    100 + 150 * depth as Score
+}
+
+/// The number of quiet moves searched before late move pruning skips the others.
+pub(crate) fn late_move_count(depth: u8) -> usize {
+    3 + depth as usize * depth as usize
+}
+
+/// How much shallower the move is searched when `moves_searched` moves came before it,
+/// leaving at least one ply.
+pub(crate) fn late_move_reduction(depth: u8, moves_searched: usize) -> u8 {
+    let reduction = 0.75 + (depth as f64).ln() * (moves_searched as f64).ln() / 2.25;
+    (reduction as u8).clamp(1, depth - 2)
+}
+
+/// With nothing but pawns, passing is often the best move there is (zugzwang), so the null
+/// move would cut nodes that are in fact lost.
+pub(crate) fn has_only_pawns(board: &ChessBoard, color: Color) -> bool {
+    (board.pieces_of(color) & !(board.pawns | board.kings)).is_empty()
+}
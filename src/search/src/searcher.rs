@@ -0,0 +1,647 @@
+use crate::pruning::{
+    FUTILITY_MAX_DEPTH, LATE_MOVE_PRUNING_MAX_DEPTH, LATE_MOVE_REDUCTIONS_MIN_DEPTH,
+    LATE_MOVE_REDUCTIONS_MIN_MOVES, NULL_MOVE_MIN_DEPTH, Pruning, RAZORING_MAX_DEPTH,
+    REVERSE_FUTILITY_MAX_DEPTH, futility_margin, has_only_pawns, late_move_count,
+    late_move_reduction, null_move_reduction, razoring_margin, reverse_futility_margin,
+};
+use board_evaluation::board_evaluator::BoardEvaluator;
+use board_representation::chess_board::{ChessBoard, SquareContent};
+use board_representation::chess_piece::{Color, PieceKind};
+use moves_generation::chess_move::Move;
+use moves_generation::move_list::MoveList;
+use moves_generation::move_picker::{MoveHistory, MovePicker, is_quiet};
+use moves_generation::valid_moves::{
+    Captures, Evasions, all_valid_moves, generate, gives_check, is_check,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+pub use transposition_table::transposition_table::MATE_THRESHOLD;
+use transposition_table::transposition_table::{
+    Bound, DEFAULT_SIZE_MB, TranspositionTable, TtEntry,
+};
+
+/// Score in centipawns from the point of view of the side to move.
+pub type Score = i32;
+
+pub const INFINITY: Score = 32000;
+pub const MATE_SCORE: Score = 31000;
+pub const MAX_DEPTH: u8 = 64;
+
+// A mate found at any depth scores above the threshold the transposition table knows mates by.
+const _: () = assert!(MATE_SCORE - MAX_DEPTH as Score > MATE_THRESHOLD);
+
+/// How many nodes are searched between two checks of the clock.
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+/// Half the width of the window around the score of the previous iteration.
+const ASPIRATION_WINDOW: Score = 50;
+/// The first iterations are searched with a full window, their scores swing too much.
+const ASPIRATION_MIN_DEPTH: u8 = 4;
+
+/// Plies of the triangular principal variation table, the quiescence search may reach
+/// `MAX_DEPTH` itself.
+const PV_TABLE_SIZE: usize = MAX_DEPTH as usize + 1;
+
+/// Margin of delta pruning: a capture is skipped when even winning the captured piece plus
+/// this much cannot bring the score up to alpha.
+const DELTA_MARGIN: Score = 200;
+
+/// Material values in centipawns, the ones of the evaluator.
+fn piece_value(kind: PieceKind) -> Score {
+    match kind {
+        PieceKind::Pawn => 100,
+        PieceKind::Knight | PieceKind::Bishop => 300,
+        PieceKind::Rook => 500,
+        PieceKind::Queen => 900,
+        PieceKind::King => 0,
+    }
+}
+
+/// Returns the number of moves until mate, negative when the side to move is getting mated.
+pub fn mate_in(score: Score) -> Option<i32> {
+    if score >= MATE_THRESHOLD {
+        Some((MATE_SCORE - score + 1) / 2)
+    } else if score <= -MATE_THRESHOLD {
+        Some(-(MATE_SCORE + score + 1) / 2)
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SearchLimits {
+    pub depth: Option<u8>,
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    /// `None` only when the side to move has no legal moves.
+    pub best_move: Option<Move>,
+    pub score: Score,
+    pub pv: Vec<Move>,
+    /// Depth of the last completed iteration.
+    pub depth: u8,
+    pub nodes: u64,
+    pub elapsed: Duration,
+    /// How full the transposition table is, in permille.
+    pub hashfull: u16,
+}
+
+pub struct Searcher {
+    evaluator: BoardEvaluator,
+    limits: SearchLimits,
+    stop: Arc<AtomicBool>,
+    transposition_table: TranspositionTable,
+    pruning: Pruning,
+    move_history: MoveHistory,
+    /// The move played at each ply of the line being searched.
+    played_moves: [Move; MAX_DEPTH as usize],
+    /// Row `ply` holds the best line found from the node at `ply`, in its columns `ply` to
+    /// `pv_length[ply]`: a node copies the row of its child behind its best move.
+    pv_table: [[Move; PV_TABLE_SIZE]; PV_TABLE_SIZE],
+    pv_length: [usize; PV_TABLE_SIZE],
+    /// Aspiration windows at the root and null windows after the first move of each node.
+    narrow_windows: bool,
+    nodes: u64,
+    start: Instant,
+    aborted: bool,
+}
+
+impl Searcher {
+    pub fn new(evaluator: BoardEvaluator, limits: SearchLimits, stop: Arc<AtomicBool>) -> Self {
+        Self::with_transposition_table(
+            evaluator,
+            limits,
+            stop,
+            TranspositionTable::new(DEFAULT_SIZE_MB),
+        )
+    }
+
+    /// A searcher reusing `transposition_table`, e.g. the one of the previous search of a game.
+    pub fn with_transposition_table(
+        evaluator: BoardEvaluator,
+        limits: SearchLimits,
+        stop: Arc<AtomicBool>,
+        transposition_table: TranspositionTable,
+    ) -> Self {
+        Self {
+            evaluator,
+            limits,
+            stop,
+            transposition_table,
+            pruning: Pruning::default(),
+            move_history: MoveHistory::new(),
+            played_moves: [Move::NULL; MAX_DEPTH as usize],
+            pv_table: [[Move::NULL; PV_TABLE_SIZE]; PV_TABLE_SIZE],
+            pv_length: [0; PV_TABLE_SIZE],
+            narrow_windows: true,
+            nodes: 0,
+            start: Instant::now(),
+            aborted: false,
+        }
+    }
+
+    /// Chooses the pruning and reductions the search uses, all of them by default.
+    pub fn with_pruning(mut self, pruning: Pruning) -> Self {
+        self.pruning = pruning;
+        self
+    }
+
+    /// Searches every move with the full window between alpha and beta, without aspiration
+    /// windows nor principal variation search. Slower, with the same results at a fixed depth
+    /// as long as nothing is pruned: the pruning compares the evaluation to the window.
+    pub fn with_full_windows(mut self) -> Self {
+        self.narrow_windows = false;
+        self
+    }
+
+    /// Gives back the transposition table, to keep its entries for the next search.
+    pub fn into_transposition_table(self) -> TranspositionTable {
+        self.transposition_table
+    }
+
+    /// Runs an iterative deepening search, calling `on_iteration` after every completed depth.
+    ///
+    /// The result of an iteration interrupted by a limit or by the stop flag is discarded,
+    /// so the returned move always comes from a fully searched depth (or is the first legal
+    /// move if not even depth 1 could be completed).
+    pub fn search<F>(&mut self, board: &ChessBoard, mut on_iteration: F) -> SearchResult
+    where
+        F: FnMut(&SearchResult),
+    {
+        self.nodes = 0;
+        self.start = Instant::now();
+        self.aborted = false;
+        self.transposition_table.new_search();
+        self.move_history.clear();
+
+        let mut root_moves = all_valid_moves(board);
+        let mut result = SearchResult {
+            best_move: root_moves.first().copied(),
+            score: 0,
+            pv: Vec::new(),
+            depth: 0,
+            nodes: 0,
+            elapsed: Duration::ZERO,
+            hashfull: 0,
+        };
+        if root_moves.is_empty() {
+            result.score = self.terminal_score(board, 0);
+            return result;
+        }
+
+        let max_depth = self.limits.depth.unwrap_or(MAX_DEPTH).clamp(1, MAX_DEPTH);
+        for depth in 1..=max_depth {
+            let Some(score) = self.aspiration_search(board, &mut root_moves, depth, result.score)
+            else {
+                break;
+            };
+            let pv = self.pv_table[0][..self.pv_length[0]].to_vec();
+            result.best_move = pv.first().copied();
+            result.score = score;
+            result.pv = pv;
+            result.depth = depth;
+            result.nodes = self.nodes;
+            result.elapsed = self.start.elapsed();
+            result.hashfull = self.transposition_table.hashfull();
+            on_iteration(&result);
+
+            // The mate is within the searched depth, looking further cannot change the outcome.
+            if score.abs() >= MATE_THRESHOLD && MATE_SCORE - score.abs() <= depth as Score {
+                break;
+            }
+        }
+        result.nodes = self.nodes;
+        result.elapsed = self.start.elapsed();
+        result.hashfull = self.transposition_table.hashfull();
+        result
+    }
+
+    /// Searches the root at `depth` in a window around `previous_score`, the score of the
+    /// previous iteration, widening it on the side the score falls out of until it lies
+    /// inside. Returns `None` if the search was aborted.
+    fn aspiration_search(
+        &mut self,
+        board: &ChessBoard,
+        root_moves: &mut [Move],
+        depth: u8,
+        previous_score: Score,
+    ) -> Option<Score> {
+        let mut delta = ASPIRATION_WINDOW;
+        let (mut alpha, mut beta) = if self.narrow_windows
+            && depth >= ASPIRATION_MIN_DEPTH
+            && previous_score.abs() < MATE_THRESHOLD
+        {
+            (previous_score - delta, previous_score + delta)
+        } else {
+            (-INFINITY, INFINITY)
+        };
+        loop {
+            let score = self.search_root(board, root_moves, depth, alpha, beta)?;
+            delta *= 2;
+            if score <= alpha && alpha > -INFINITY {
+                alpha = (alpha - delta).max(-INFINITY);
+            } else if score >= beta && beta < INFINITY {
+                beta = (beta + delta).min(INFINITY);
+            } else {
+                return Some(score);
+            }
+        }
+    }
+
+    /// Searches all the root moves at `depth`, returns `None` if the search was aborted.
+    /// The best move is moved to the front of `root_moves` so it is searched first next time.
+    fn search_root(
+        &mut self,
+        board: &ChessBoard,
+        root_moves: &mut [Move],
+        depth: u8,
+        mut alpha: Score,
+        beta: Score,
+    ) -> Option<Score> {
+        self.pv_length[0] = 0;
+        let mut best_index = None;
+        for (index, mov) in root_moves.iter().enumerate() {
+            let mut board_after_move = *board;
+            mov.move_piece(&mut board_after_move);
+            self.played_moves[0] = *mov;
+            let late = (index > 0).then_some(0);
+            let score = self.search_move(&board_after_move, depth - 1, 1, alpha, beta, late);
+            if self.aborted {
+                return None;
+            }
+            if score > alpha {
+                alpha = score;
+                best_index = Some(index);
+                self.update_pv(0, *mov);
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+        if let Some(best_index) = best_index {
+            root_moves[..=best_index].rotate_right(1);
+        }
+        Some(alpha)
+    }
+
+    /// Searches the position reached by a move, `depth` plies deep, and returns its score
+    /// for the side that played the move.
+    ///
+    /// The first move of a node, with `late` set to `None`, gets the whole window. The later
+    /// ones only have to show they do not beat alpha: they are searched `late` plies less
+    /// deep with a null window, then at full depth with a null window, and only the moves
+    /// beating alpha are searched again with the whole window.
+    fn search_move(
+        &mut self,
+        board: &ChessBoard,
+        depth: u8,
+        ply: u8,
+        alpha: Score,
+        beta: Score,
+        late: Option<u8>,
+    ) -> Score {
+        let mut score = alpha + 1;
+        if let Some(reduction) = late
+            && reduction > 0
+        {
+            score = -self.negamax(board, depth - reduction, ply, -alpha - 1, -alpha);
+        }
+        if late.is_some() && self.narrow_windows && score > alpha {
+            score = -self.negamax(board, depth, ply, -alpha - 1, -alpha);
+            if score >= beta {
+                return score;
+            }
+        }
+        if score > alpha {
+            score = -self.negamax(board, depth, ply, -beta, -alpha);
+        }
+        score
+    }
+
+    /// Makes `mov` the best move at `ply`, followed by the best line of the child node.
+    fn update_pv(&mut self, ply: usize, mov: Move) {
+        let child_length = self.pv_length[ply + 1];
+        let (rows, child_rows) = self.pv_table.split_at_mut(ply + 1);
+        rows[ply][ply] = mov;
+        rows[ply][ply + 1..child_length].copy_from_slice(&child_rows[0][ply + 1..child_length]);
+        self.pv_length[ply] = child_length;
+    }
+
+    fn negamax(
+        &mut self,
+        board: &ChessBoard,
+        depth: u8,
+        ply: u8,
+        mut alpha: Score,
+        beta: Score,
+    ) -> Score {
+        if depth == 0 {
+            return self.quiescence(board, ply, alpha, beta);
+        }
+        self.pv_length[ply as usize] = ply as usize;
+
+        self.nodes += 1;
+        if self.should_stop() {
+            self.aborted = true;
+            return 0;
+        }
+
+        let tt_entry = self.transposition_table.probe(board, ply);
+        if let Some(entry) = tt_entry
+            && entry.depth >= depth
+        {
+            // Only scores outside the window cut, an exact score inside it would leave the
+            // principal variation without its continuation.
+            let cutoff = match entry.bound {
+                Bound::Exact => entry.score >= beta || entry.score <= alpha,
+                Bound::Lower => entry.score >= beta,
+                Bound::Upper => entry.score <= alpha,
+            };
+            if cutoff {
+                return entry.score;
+            }
+        }
+
+        let previous_move = self.played_moves[ply as usize - 1];
+        // The best move of an earlier search of the position goes first.
+        let moves = MovePicker::new(
+            board,
+            tt_entry.and_then(|entry| entry.best_move),
+            ply as usize,
+            (previous_move != Move::NULL).then_some(previous_move),
+            &self.move_history,
+        );
+        if moves.is_empty() {
+            return self.terminal_score(board, ply);
+        }
+        if ply >= MAX_DEPTH {
+            return self.evaluate(board);
+        }
+
+        // Nothing is pruned when in check, and nodes are only cut on the static evaluation
+        // when neither bound is a mate.
+        let in_check = is_check(board);
+        let prunable = !in_check;
+        let static_eval = self.evaluate(board);
+        let no_mate_bound = alpha.abs() < MATE_THRESHOLD && beta.abs() < MATE_THRESHOLD;
+
+        if prunable
+            && no_mate_bound
+            && self.pruning.reverse_futility
+            && depth <= REVERSE_FUTILITY_MAX_DEPTH
+            && static_eval - reverse_futility_margin(depth) >= beta
+        {
+            return static_eval;
+        }
+
+        if prunable
+            && no_mate_bound
+            && self.pruning.razoring
+            && depth <= RAZORING_MAX_DEPTH
+            && static_eval + razoring_margin(depth) <= alpha
+        {
+            let score = self.quiescence(board, ply, alpha, alpha + 1);
+            if self.aborted {
+                return 0;
+            }
+            if score <= alpha {
+                return score;
+            }
+        }
+
+        if prunable
+            && self.pruning.null_move
+            && depth >= NULL_MOVE_MIN_DEPTH
+            && static_eval >= beta
+            && previous_move != Move::NULL
+            && !has_only_pawns(board, board.current_turn())
+        {
+            let mut board_after_null_move = *board;
+            board_after_null_move.set_en_passant_target_square(None);
+            board_after_null_move.next_turn();
+            self.played_moves[ply as usize] = Move::NULL;
+            let reduced_depth = (depth - 1).saturating_sub(null_move_reduction(depth));
+            let score = -self.negamax(
+                &board_after_null_move,
+                reduced_depth,
+                ply + 1,
+                -beta,
+                -beta + 1,
+            );
+            if self.aborted {
+                return 0;
+            }
+            if score >= beta {
+                // A mate found after passing is not a mate of this position.
+                return if score >= MATE_THRESHOLD { beta } else { score };
+            }
+        }
+
+        let original_alpha = alpha;
+        let mut best_move = None;
+        let mut tried_quiets = MoveList::new();
+        let mut moves_searched = 0;
+        for mov in moves {
+            let quiet = is_quiet(mov);
+            // Quiet moves giving check are tactics, they are neither pruned nor reduced.
+            let quiet_no_check = quiet && !gives_check(board, mov);
+            if prunable && quiet_no_check && moves_searched > 0 && alpha > -MATE_THRESHOLD {
+                if self.pruning.futility
+                    && depth <= FUTILITY_MAX_DEPTH
+                    && static_eval + futility_margin(depth) <= alpha
+                {
+                    continue;
+                }
+                if self.pruning.late_move_pruning
+                    && depth <= LATE_MOVE_PRUNING_MAX_DEPTH
+                    && tried_quiets.len() >= late_move_count(depth)
+                {
+                    continue;
+                }
+            }
+
+            let mut board_after_move = *board;
+            mov.move_piece(&mut board_after_move);
+            self.played_moves[ply as usize] = mov;
+
+            let reduction = if self.pruning.late_move_reductions
+                && prunable
+                && quiet_no_check
+                && depth >= LATE_MOVE_REDUCTIONS_MIN_DEPTH
+                && moves_searched >= LATE_MOVE_REDUCTIONS_MIN_MOVES
+            {
+                late_move_reduction(depth, moves_searched)
+            } else {
+                0
+            };
+            let late = (moves_searched > 0).then_some(reduction);
+            let score = self.search_move(&board_after_move, depth - 1, ply + 1, alpha, beta, late);
+            if self.aborted {
+                return 0;
+            }
+            moves_searched += 1;
+
+            if score > alpha {
+                alpha = score;
+                best_move = Some(mov);
+                self.update_pv(ply as usize, mov);
+                if alpha >= beta {
+                    if quiet {
+                        self.move_history.record_cutoff(
+                            board.current_turn(),
+                            ply as usize,
+                            depth,
+                            mov,
+                            (previous_move != Move::NULL).then_some(previous_move),
+                            &tried_quiets,
+                        );
+                    }
+                    break;
+                }
+            }
+            if quiet {
+                tried_quiets.push(mov);
+            }
+        }
+
+        let bound = if alpha >= beta {
+            Bound::Lower
+        } else if alpha > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        self.transposition_table.store(
+            board,
+            ply,
+            TtEntry {
+                best_move,
+                score: alpha,
+                depth,
+                bound,
+            },
+        );
+        alpha
+    }
+
+    /// Extends the leaves of the search with captures and queen promotions until the position
+    /// is quiet, so a piece about to be taken back is not counted as won.
+    ///
+    /// The side to move may stand pat on the static evaluation, since it is not forced to
+    /// capture, except when in check: then every evasion is searched and mates are found.
+    fn quiescence(&mut self, board: &ChessBoard, ply: u8, mut alpha: Score, beta: Score) -> Score {
+        self.pv_length[ply as usize] = ply as usize;
+        self.nodes += 1;
+        if self.should_stop() {
+            self.aborted = true;
+            return 0;
+        }
+        if ply >= MAX_DEPTH {
+            return self.evaluate(board);
+        }
+
+        let in_check = is_check(board);
+        let stand_pat = self.evaluate(board);
+        let mut moves = if in_check {
+            generate::<Evasions>(board)
+        } else {
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+            generate::<Captures>(board)
+        };
+        if in_check && moves.is_empty() {
+            return -MATE_SCORE + ply as Score;
+        }
+        // Most valuable victim first, then least valuable attacker.
+        moves.sort_by_score(|mov| {
+            material_gain(board, *mov) - piece_value(attacker_kind(board, *mov)) / 10
+        });
+
+        for mov in moves {
+            if !in_check {
+                // Underpromotions are never better than the queen, quiet ones are not tactics.
+                if mov
+                    .promoted_piece_kind()
+                    .is_some_and(|kind| kind != PieceKind::Queen)
+                {
+                    continue;
+                }
+                if stand_pat + material_gain(board, mov) + DELTA_MARGIN <= alpha {
+                    continue;
+                }
+            }
+            let mut board_after_move = *board;
+            mov.move_piece(&mut board_after_move);
+            let score = -self.quiescence(&board_after_move, ply + 1, -beta, -alpha);
+            if self.aborted {
+                return 0;
+            }
+            if score > alpha {
+                alpha = score;
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+        alpha
+    }
+
+    /// Score of a position without legal moves: checkmate (the sooner, the worse) or stalemate.
+    fn terminal_score(&self, board: &ChessBoard, ply: u8) -> Score {
+        if is_check(board) {
+            -MATE_SCORE + ply as Score
+        } else {
+            0
+        }
+    }
+
+    fn evaluate(&self, board: &ChessBoard) -> Score {
+        let score = (self.evaluator.eval_board(board) * 100.0).round() as Score;
+        match board.current_turn() {
+            Color::White => score,
+            Color::Black => -score,
+        }
+    }
+
+    fn should_stop(&self) -> bool {
+        if self.stop.load(Ordering::Relaxed) {
+            return true;
+        }
+        if self.limits.nodes.is_some_and(|nodes| self.nodes > nodes) {
+            return true;
+        }
+        self.nodes.is_multiple_of(TIME_CHECK_INTERVAL)
+            && self
+                .limits
+                .time
+                .is_some_and(|time| self.start.elapsed() >= time)
+    }
+}
+
+/// The material `mov` wins on the spot: the captured piece and what a pawn promotes to.
+fn material_gain(board: &ChessBoard, mov: Move) -> Score {
+    let captured = if mov.is_en_passant() {
+        piece_value(PieceKind::Pawn)
+    } else if mov.is_capture() {
+        match board.at(mov.to()) {
+            SquareContent::Occupied(piece) => piece_value(piece.kind),
+            SquareContent::Empty => 0,
+        }
+    } else {
+        0
+    };
+    let promoted = mov
+        .promoted_piece_kind()
+        .map_or(0, |kind| piece_value(kind) - piece_value(PieceKind::Pawn));
+    captured + promoted
+}
+
+fn attacker_kind(board: &ChessBoard, mov: Move) -> PieceKind {
+    board.piece_at_source_or_panic(mov.from()).kind
+}
@@ -0,0 +1,383 @@
+#[cfg(test)]
+mod search {
+    use board_evaluation::board_evaluator::BoardEvaluator;
+    use board_representation::chess_board::ChessBoard;
+    use moves_generation::valid_moves::all_valid_moves;
+    use search::pruning::Pruning;
+    use search::searcher::{MATE_SCORE, SearchLimits, SearchResult, Searcher, mate_in};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use std::time::{Duration, Instant};
+
+    fn evaluator() -> BoardEvaluator {
+        BoardEvaluator {
+            eval_material: true,
+            eval_doubled_pawns: true,
+            eval_isolated_pawns: true,
+            eval_blocked_pawns: true,
+        }
+    }
+
+    fn search(fen: &str, limits: SearchLimits) -> SearchResult {
+        let board = ChessBoard::from_str(fen).unwrap();
+        let mut searcher = Searcher::new(evaluator(), limits, Arc::new(AtomicBool::new(false)));
+        searcher.search(&board, |_| {})
+    }
+
+    fn search_depth(fen: &str, depth: u8) -> SearchResult {
+        search(
+            fen,
+            SearchLimits {
+                depth: Some(depth),
+                ..SearchLimits::default()
+            },
+        )
+    }
+
+    fn search_with_pruning(fen: &str, depth: u8, pruning: Pruning) -> SearchResult {
+        let board = ChessBoard::from_str(fen).unwrap();
+        let limits = SearchLimits {
+            depth: Some(depth),
+            ..SearchLimits::default()
+        };
+        let mut searcher = Searcher::new(evaluator(), limits, Arc::new(AtomicBool::new(false)))
+            .with_pruning(pruning);
+        searcher.search(&board, |_| {})
+    }
+
+    fn best_move(result: &SearchResult) -> String {
+        result.best_move.unwrap().to_uci_string()
+    }
+
+    mod tactics {
+        use super::*;
+
+        #[test]
+        fn mate_in_1() {
+            let result = search_depth("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", 3);
+            assert_eq!(best_move(&result), "a1a8");
+            assert_eq!(mate_in(result.score), Some(1));
+            assert_eq!(result.pv.len(), 1);
+        }
+
+        #[test]
+        fn mate_in_2() {
+            // 1. Ra6 bxa6 2. b7#
+            let result = search_depth("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1", 4);
+            assert_eq!(best_move(&result), "a1a6");
+            assert_eq!(mate_in(result.score), Some(2));
+            let pv = result
+                .pv
+                .iter()
+                .map(|mov| mov.to_uci_string())
+                .collect::<Vec<_>>();
+            assert_eq!(pv, vec!["a1a6", "b7a6", "b6b7"]);
+        }
+
+        #[test]
+        fn getting_mated() {
+            // 1... Kb8 2. Rh8#
+            let result = search_depth("k7/8/1K6/8/8/8/8/7R b - - 0 1", 4);
+            assert_eq!(mate_in(result.score), Some(-1));
+        }
+
+        #[test]
+        fn takes_hanging_queen() {
+            let result = search_depth("4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1", 2);
+            assert_eq!(best_move(&result), "e4d5");
+        }
+
+        #[test]
+        fn avoids_poisoned_pawn() {
+            // Qxb7 wins a pawn but loses the queen to Rxb7.
+            let result = search_depth("1r2k3/1p6/8/8/8/8/8/1Q2K3 w - - 0 1", 2);
+            assert_ne!(best_move(&result), "b1b7");
+        }
+    }
+
+    mod quiescence {
+        use super::*;
+
+        #[test]
+        fn defended_pawn() {
+            // Qxd6 wins a pawn for a horizon of one ply, and loses the queen to cxd6.
+            let result = search_depth("4k3/2p5/3p4/8/8/8/3Q4/4K3 w - - 0 1", 1);
+            assert_ne!(best_move(&result), "d2d6");
+            assert!(result.score > 0);
+        }
+
+        #[test]
+        fn pawn_defended_by_rook() {
+            // Qxb7 wins the pawn on b7 and is met by Rxb7, though the static evaluation after it
+            // is a pawn up.
+            let result = search_depth("1r2k3/1p6/8/8/8/8/8/1Q2K3 w - - 0 1", 1);
+            assert_ne!(best_move(&result), "b1b7");
+        }
+
+        #[test]
+        fn exchanges_are_resolved() {
+            // Rxd5 Rxd5 Rxd5 wins a pawn: two attackers against one defender.
+            let result = search_depth("3rk3/8/8/3p4/8/8/8/3RK2R w - - 0 1", 1);
+            assert_ne!(best_move(&result), "d1d5");
+            let result = search_depth("3rk3/8/8/3p4/8/8/3R4/3RK3 w - - 0 1", 1);
+            assert_eq!(best_move(&result), "d2d5");
+        }
+
+        #[test]
+        fn promotions() {
+            // The pawn queens right after the horizon unless the king covers b8.
+            let result = search_depth("8/1P1k4/8/8/8/8/8/6K1 b - - 0 1", 1);
+            assert!(["d7c7", "d7c8"].contains(&best_move(&result).as_str()));
+        }
+
+        #[test]
+        fn check_evasions() {
+            // Qxf7 mates at the horizon, where the king in check cannot stand pat.
+            let result = search_depth(
+                "r1bqkbnr/pppp1ppp/2n5/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR b KQkq - 3 3",
+                1,
+            );
+            assert!(["g7g6", "d8e7", "d8f6"].contains(&best_move(&result).as_str()));
+            assert!(mate_in(result.score).is_none());
+        }
+    }
+
+    mod pruning {
+        use super::*;
+
+        #[test]
+        fn mates_in_n() {
+            let positions = [
+                ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8", 1),
+                ("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1", "a1a6", 2),
+                (
+                    "r2qkb1r/pp2nppp/3p4/2pNN1B1/2BnP3/3P4/PPP2PPP/R2bK2R w KQkq - 1 1",
+                    "d5f6",
+                    2,
+                ),
+                (
+                    "r1b1kb1r/pppp1ppp/5q2/4n3/3KP3/2N3PN/PPP4P/R1BQ1B1R b kq - 0 1",
+                    "f8c5",
+                    3,
+                ),
+                ("8/8/8/8/8/5K2/6R1/7k w - - 0 1", "g2f2", 3),
+                (
+                    "r1bqr3/ppp1B1kp/1b4p1/n2B4/3PQ1P1/2P5/P4P2/RN4K1 w - - 1 1",
+                    "e4e5",
+                    4,
+                ),
+            ];
+            for (fen, mating_move, moves_to_mate) in positions {
+                // Reductions may hide the mate until a ply or two past its length.
+                let result = search_with_pruning(fen, 2 * moves_to_mate + 1, Pruning::ALL);
+                assert_eq!(mate_in(result.score), Some(moves_to_mate as i32), "{}", fen);
+                assert_eq!(best_move(&result), mating_move, "{}", fen);
+            }
+        }
+
+        #[test]
+        fn fewer_nodes() {
+            let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+            let full_width = search_with_pruning(fen, 5, Pruning::NONE);
+            let selective = search_with_pruning(fen, 5, Pruning::ALL);
+            assert!(selective.nodes < full_width.nodes);
+            for pruning in [
+                Pruning {
+                    null_move: true,
+                    ..Pruning::NONE
+                },
+                Pruning {
+                    late_move_reductions: true,
+                    ..Pruning::NONE
+                },
+                Pruning {
+                    reverse_futility: true,
+                    ..Pruning::NONE
+                },
+                Pruning {
+                    futility: true,
+                    ..Pruning::NONE
+                },
+                Pruning {
+                    razoring: true,
+                    ..Pruning::NONE
+                },
+                Pruning {
+                    late_move_pruning: true,
+                    ..Pruning::NONE
+                },
+            ] {
+                let result = search_with_pruning(fen, 5, pruning);
+                assert!(result.nodes < full_width.nodes, "{:?}", pruning);
+            }
+        }
+
+        #[test]
+        fn no_null_move_in_pawn_endgames() {
+            // Passing would be the best move of both sides here, the null move is never tried.
+            let fen = "8/8/1p6/1Pk5/2p5/2P5/2K5/8 w - - 0 1";
+            let null_move_only = Pruning {
+                null_move: true,
+                ..Pruning::NONE
+            };
+            let with_null_move = search_with_pruning(fen, 8, null_move_only);
+            let without = search_with_pruning(fen, 8, Pruning::NONE);
+            assert_eq!(with_null_move.nodes, without.nodes);
+            assert_eq!(with_null_move.score, without.score);
+        }
+    }
+
+    mod windows {
+        use super::*;
+
+        const POSITIONS: [&str; 8] = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+            "kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1",
+            "1r2k3/1p6/8/8/8/8/8/1Q2K3 w - - 0 1",
+            "r1b1kb1r/pppp1ppp/5q2/4n3/3KP3/2N3PN/PPP4P/R1BQ1B1R b kq - 0 1",
+        ];
+
+        fn search_fixed_depth(fen: &str, depth: u8, full_windows: bool) -> SearchResult {
+            let board = ChessBoard::from_str(fen).unwrap();
+            let limits = SearchLimits {
+                depth: Some(depth),
+                ..SearchLimits::default()
+            };
+            let searcher = Searcher::new(evaluator(), limits, Arc::new(AtomicBool::new(false)))
+                .with_pruning(Pruning::NONE);
+            let mut searcher = if full_windows {
+                searcher.with_full_windows()
+            } else {
+                searcher
+            };
+            searcher.search(&board, |_| {})
+        }
+
+        #[test]
+        fn same_results_as_full_windows() {
+            // Pruning compares the evaluation to the window, only the exact search is bound to
+            // give the same answers whatever the window.
+            let (mut narrow_nodes, mut full_nodes) = (0, 0);
+            for fen in POSITIONS {
+                for depth in 1..=5 {
+                    let narrow = search_fixed_depth(fen, depth, false);
+                    let full = search_fixed_depth(fen, depth, true);
+                    assert_eq!(
+                        narrow.best_move, full.best_move,
+                        "{} at depth {}",
+                        fen, depth
+                    );
+                    assert_eq!(narrow.score, full.score, "{} at depth {}", fen, depth);
+                    narrow_nodes += narrow.nodes;
+                    full_nodes += full.nodes;
+                }
+            }
+            assert!(narrow_nodes < full_nodes);
+        }
+
+        #[test]
+        fn principal_variation_of_every_iteration() {
+            let board = ChessBoard::from_str(POSITIONS[1]).unwrap();
+            let limits = SearchLimits {
+                depth: Some(6),
+                ..SearchLimits::default()
+            };
+            let mut searcher = Searcher::new(evaluator(), limits, Arc::new(AtomicBool::new(false)));
+            let mut iterations = Vec::new();
+            let result = searcher.search(&board, |iteration| iterations.push(iteration.clone()));
+            assert_eq!(iterations.len(), 6);
+            for iteration in &iterations {
+                assert_eq!(iteration.pv.first().copied(), iteration.best_move);
+                assert!(!iteration.pv.is_empty() && iteration.pv.len() <= iteration.depth as usize);
+                // Every move of the line is legal in the position it is played from.
+                let mut position = board;
+                for mov in &iteration.pv {
+                    assert!(all_valid_moves(&position).contains(mov));
+                    mov.move_piece(&mut position);
+                }
+            }
+            assert_eq!(result.pv, iterations.last().unwrap().pv);
+        }
+    }
+
+    mod terminal_positions {
+        use super::*;
+
+        #[test]
+        fn checkmated() {
+            let result = search_depth("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1", 3);
+            assert!(result.best_move.is_none());
+            assert_eq!(result.score, -MATE_SCORE);
+        }
+
+        #[test]
+        fn stalemate() {
+            let result = search_depth("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", 3);
+            assert!(result.best_move.is_none());
+            assert_eq!(result.score, 0);
+        }
+    }
+
+    mod limits {
+        use super::*;
+
+        #[test]
+        fn depth_limit() {
+            let board = ChessBoard::default();
+            let limits = SearchLimits {
+                depth: Some(3),
+                ..SearchLimits::default()
+            };
+            let mut searcher = Searcher::new(evaluator(), limits, Arc::new(AtomicBool::new(false)));
+            let mut depths = Vec::new();
+            let result = searcher.search(&board, |iteration| depths.push(iteration.depth));
+            assert_eq!(depths, vec![1, 2, 3]);
+            assert_eq!(result.depth, 3);
+            assert_eq!(result.pv.len(), 3);
+            assert!(result.best_move.is_some());
+        }
+
+        #[test]
+        fn node_limit() {
+            let result = search(
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                SearchLimits {
+                    nodes: Some(5000),
+                    ..SearchLimits::default()
+                },
+            );
+            assert!(result.nodes <= 5001);
+            assert!(result.depth >= 1);
+            assert!(result.best_move.is_some());
+        }
+
+        #[test]
+        fn time_limit() {
+            let start = Instant::now();
+            let result = search(
+                "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+                SearchLimits {
+                    time: Some(Duration::from_millis(200)),
+                    ..SearchLimits::default()
+                },
+            );
+            assert!(start.elapsed() < Duration::from_secs(2));
+            assert!(result.best_move.is_some());
+        }
+
+        #[test]
+        fn stop_flag() {
+            let board = ChessBoard::default();
+            let stop = Arc::new(AtomicBool::new(true));
+            let mut searcher = Searcher::new(evaluator(), SearchLimits::default(), stop);
+            let result = searcher.search(&board, |_| panic!("No iteration should complete"));
+            assert_eq!(result.depth, 0);
+            assert!(result.best_move.is_some());
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod transposition_table;
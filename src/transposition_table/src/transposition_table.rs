@@ -0,0 +1,249 @@
+use board_representation::chess_board::ChessBoard;
+use board_representation::zobrist::ZobristHash;
+use moves_generation::chess_move::Move;
+
+/// Scores beyond this, in absolute value, are mates. The search counts them in plies from the
+/// root and the table from the position of the entry, so a mate found through one move order
+/// keeps the right distance when the position is reached through another.
+pub const MATE_THRESHOLD: i32 = 30000;
+
+pub const DEFAULT_SIZE_MB: usize = 16;
+
+const SLOTS_PER_BUCKET: usize = 4;
+/// Ages are stored on 6 bits, next to the 2 bits of the bound.
+const AGE_CYCLE: u8 = 64;
+/// How many plies of depth one search of age is worth when choosing the entry to overwrite.
+const AGE_WEIGHT: i32 = 8;
+
+/// How the score of an entry relates to the real score of the position.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Bound {
+    /// The score was inside the search window.
+    Exact,
+    /// The search failed high, the real score is at least this.
+    Lower,
+    /// The search failed low, the real score is at most this.
+    Upper,
+}
+
+/// How `store` chooses between a new entry and the one of the bucket it would overwrite.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Replacement {
+    /// Entries of the current search are only overwritten by deeper or exact ones.
+    #[default]
+    DepthPreferred,
+    /// The new entry always goes in, over the least valuable entry of the bucket.
+    AlwaysReplace,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TtEntry {
+    pub best_move: Option<Move>,
+    /// From the point of view of the side to move, mates counted from the root like in the
+    /// search.
+    pub score: i32,
+    pub depth: u8,
+    pub bound: Bound,
+}
+
+/// An entry packed in 8 bytes.
+#[derive(Clone, Copy, Default)]
+struct Slot {
+    /// The low 16 bits of the hash, the high bits select the bucket.
+    key: u16,
+    /// `Move::NULL` when there is no best move.
+    best_move: u16,
+    /// Mates counted from the position of the entry.
+    score: i16,
+    depth: u8,
+    /// The bound in bits 0-1 (0 for an empty slot) and the age in bits 2-7.
+    bound_and_age: u8,
+}
+
+impl Slot {
+    fn is_empty(&self) -> bool {
+        self.bound_and_age & 3 == 0
+    }
+
+    fn age(&self) -> u8 {
+        self.bound_and_age >> 2
+    }
+
+    fn bound(&self) -> Bound {
+        match self.bound_and_age & 3 {
+            1 => Bound::Exact,
+            2 => Bound::Lower,
+            _ => Bound::Upper,
+        }
+    }
+}
+
+/// Slots sharing a cache line, probed together.
+#[derive(Clone, Copy, Default)]
+#[repr(align(32))]
+struct Bucket([Slot; SLOTS_PER_BUCKET]);
+
+/// Search results of positions already visited, keyed by the Zobrist hash of the board.
+pub struct TranspositionTable {
+    buckets: Vec<Bucket>,
+    replacement: Replacement,
+    /// Incremented by `new_search`, so entries of older searches are overwritten first.
+    age: u8,
+}
+
+fn score_to_table(score: i32, ply: u8) -> i16 {
+    let score = if score >= MATE_THRESHOLD {
+        score + ply as i32
+    } else if score <= -MATE_THRESHOLD {
+        score - ply as i32
+    } else {
+        score
+    };
+    score as i16
+}
+
+fn score_from_table(score: i16, ply: u8) -> i32 {
+    let score = score as i32;
+    if score >= MATE_THRESHOLD {
+        score - ply as i32
+    } else if score <= -MATE_THRESHOLD {
+        score + ply as i32
+    } else {
+        score
+    }
+}
+
+impl TranspositionTable {
+    /// A table of `size_mb` megabytes replacing entries with `Replacement::DepthPreferred`.
+    /// The table always has room for at least one bucket of entries.
+    pub fn new(size_mb: usize) -> Self {
+        Self::with_replacement(size_mb, Replacement::default())
+    }
+
+    pub fn with_replacement(size_mb: usize, replacement: Replacement) -> Self {
+        let mut table = Self {
+            buckets: Vec::new(),
+            replacement,
+            age: 0,
+        };
+        table.resize(size_mb);
+        table
+    }
+
+    /// Reallocates the table to `size_mb` megabytes, dropping every entry.
+    pub fn resize(&mut self, size_mb: usize) {
+        let bucket_count = (size_mb * 1024 * 1024 / size_of::<Bucket>()).max(1);
+        self.buckets = vec![Bucket::default(); bucket_count];
+        self.age = 0;
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.fill(Bucket::default());
+        self.age = 0;
+    }
+
+    /// The number of entries the table can hold.
+    pub fn capacity(&self) -> usize {
+        self.buckets.len() * SLOTS_PER_BUCKET
+    }
+
+    /// Ages the entries, to be called before each search.
+    pub fn new_search(&mut self) {
+        self.age = (self.age + 1) % AGE_CYCLE;
+    }
+
+    /// How full the table is, in permille, estimated from the entries of the current search
+    /// in the first thousand slots. UCI reports it as `hashfull`.
+    pub fn hashfull(&self) -> u16 {
+        let slots = self
+            .buckets
+            .iter()
+            .take(1000 / SLOTS_PER_BUCKET)
+            .flat_map(|bucket| bucket.0.iter());
+        let (mut sampled, mut used) = (0, 0);
+        for slot in slots {
+            sampled += 1;
+            if !slot.is_empty() && slot.age() == self.age {
+                used += 1;
+            }
+        }
+        (used * 1000 / sampled) as u16
+    }
+
+    fn bucket_index(&self, hash: ZobristHash) -> usize {
+        // The high bits of the product map the hash evenly on any number of buckets.
+        ((hash as u128 * self.buckets.len() as u128) >> 64) as usize
+    }
+
+    /// The entry of `board`, reached `ply` plies from the root, if the table has one.
+    pub fn probe(&self, board: &ChessBoard, ply: u8) -> Option<TtEntry> {
+        let hash = board.hash();
+        let key = hash as u16;
+        let slot = self.buckets[self.bucket_index(hash)]
+            .0
+            .iter()
+            .find(|slot| !slot.is_empty() && slot.key == key)?;
+        let best_move = Move::from_bits(slot.best_move);
+        Some(TtEntry {
+            best_move: (best_move != Move::NULL).then_some(best_move),
+            score: score_from_table(slot.score, ply),
+            depth: slot.depth,
+            bound: slot.bound(),
+        })
+    }
+
+    /// Records the search result `entry` of `board`, reached `ply` plies from the root.
+    ///
+    /// The entry goes over the one of the same position, else over an empty slot, else over
+    /// the shallowest and oldest entry of the bucket, unless `Replacement::DepthPreferred`
+    /// keeps it.
+    pub fn store(&mut self, board: &ChessBoard, ply: u8, entry: TtEntry) {
+        let hash = board.hash();
+        let key = hash as u16;
+        let (age, replacement) = (self.age, self.replacement);
+        let index = self.bucket_index(hash);
+        let slots = &mut self.buckets[index].0;
+
+        let value = |slot: &Slot| {
+            if slot.is_empty() {
+                return i32::MIN;
+            }
+            let relative_age = ((AGE_CYCLE + age - slot.age()) % AGE_CYCLE) as i32;
+            slot.depth as i32 - AGE_WEIGHT * relative_age
+        };
+        let victim_index = slots
+            .iter()
+            .position(|slot| !slot.is_empty() && slot.key == key)
+            .or_else(|| (0..SLOTS_PER_BUCKET).min_by_key(|index| value(&slots[*index])))
+            .expect("Buckets are never empty");
+        let victim = &mut slots[victim_index];
+
+        let same_position = !victim.is_empty() && victim.key == key;
+        let keeps_victim = !victim.is_empty()
+            && victim.age() == age
+            && entry.depth < victim.depth
+            && !(same_position && entry.bound == Bound::Exact);
+        if replacement == Replacement::DepthPreferred && keeps_victim {
+            return;
+        }
+
+        let best_move = match entry.best_move {
+            Some(mov) => mov.to_bits(),
+            // A shallower search of the same position may not have found a move.
+            None if same_position => victim.best_move,
+            None => Move::NULL.to_bits(),
+        };
+        let bound = match entry.bound {
+            Bound::Exact => 1,
+            Bound::Lower => 2,
+            Bound::Upper => 3,
+        };
+        *victim = Slot {
+            key,
+            best_move,
+            score: score_to_table(entry.score, ply),
+            depth: entry.depth,
+            bound_and_age: bound | age << 2,
+        };
+    }
+}
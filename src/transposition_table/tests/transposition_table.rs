@@ -0,0 +1,164 @@
+use ::transposition_table::transposition_table::{
+    Bound, MATE_THRESHOLD, Replacement, TranspositionTable, TtEntry,
+};
+use board_representation::chess_board::ChessBoard;
+use moves_generation::chess_move::{MakeMove, Move};
+use moves_generation::valid_moves::all_valid_moves;
+
+/// The 20 positions after the first move of the game, all different.
+fn positions() -> Vec<(ChessBoard, Move)> {
+    let board = ChessBoard::default();
+    all_valid_moves(&board)
+        .into_iter()
+        .map(|mov| {
+            let mut board_after_move = board;
+            board_after_move.make_move(mov);
+            (board_after_move, mov)
+        })
+        .collect()
+}
+
+fn entry(best_move: Option<Move>, score: i32, depth: u8, bound: Bound) -> TtEntry {
+    TtEntry {
+        best_move,
+        score,
+        depth,
+        bound,
+    }
+}
+
+#[cfg(test)]
+mod transposition_table {
+    use super::*;
+
+    #[test]
+    fn store_and_probe() {
+        let mut table = TranspositionTable::new(1);
+        let positions = positions();
+        for (depth, (board, mov)) in positions.iter().enumerate() {
+            table.store(
+                board,
+                0,
+                entry(Some(*mov), depth as i32 - 10, depth as u8, Bound::Exact),
+            );
+        }
+        for (depth, (board, mov)) in positions.iter().enumerate() {
+            assert_eq!(
+                table.probe(board, 0),
+                Some(entry(
+                    Some(*mov),
+                    depth as i32 - 10,
+                    depth as u8,
+                    Bound::Exact
+                ))
+            );
+        }
+        assert_eq!(table.probe(&ChessBoard::default(), 0), None);
+
+        table.clear();
+        assert_eq!(table.probe(&positions[0].0, 0), None);
+    }
+
+    #[test]
+    fn size_in_megabytes() {
+        assert_eq!(TranspositionTable::new(1).capacity(), 1024 * 1024 / 8);
+        assert_eq!(TranspositionTable::new(16).capacity(), 16 * 1024 * 1024 / 8);
+        // Never smaller than a bucket.
+        assert_eq!(TranspositionTable::new(0).capacity(), 4);
+
+        let mut table = TranspositionTable::new(1);
+        let (board, mov) = positions()[0];
+        table.store(&board, 0, entry(Some(mov), 0, 1, Bound::Lower));
+        table.resize(2);
+        assert_eq!(table.capacity(), 2 * 1024 * 1024 / 8);
+        assert_eq!(table.probe(&board, 0), None);
+    }
+
+    #[test]
+    fn mate_scores_are_relative_to_the_ply() {
+        let mut table = TranspositionTable::new(1);
+        let (board, _) = positions()[0];
+        // Mate in 3 plies from a position 5 plies away from the root: 8 plies from the root.
+        let mate = 31000;
+        table.store(&board, 5, entry(None, mate - 8, 4, Bound::Exact));
+        assert_eq!(table.probe(&board, 5).unwrap().score, mate - 8);
+        // Reached 2 plies from the root, the mate is 5 plies away.
+        assert_eq!(table.probe(&board, 2).unwrap().score, mate - 5);
+
+        table.store(&board, 3, entry(None, -mate + 10, 6, Bound::Upper));
+        assert_eq!(table.probe(&board, 1).unwrap().score, -mate + 8);
+
+        // Ordinary scores are left alone.
+        table.store(&board, 3, entry(None, MATE_THRESHOLD - 1, 8, Bound::Lower));
+        assert_eq!(table.probe(&board, 7).unwrap().score, MATE_THRESHOLD - 1);
+    }
+
+    #[test]
+    fn keeps_the_best_move_of_the_position() {
+        let mut table = TranspositionTable::new(1);
+        let (board, mov) = positions()[0];
+        table.store(&board, 0, entry(Some(mov), 30, 2, Bound::Lower));
+        table.store(&board, 0, entry(None, -20, 3, Bound::Upper));
+        assert_eq!(
+            table.probe(&board, 0),
+            Some(entry(Some(mov), -20, 3, Bound::Upper))
+        );
+    }
+
+    #[test]
+    fn depth_preferred_replacement() {
+        // A single bucket of 4 slots, shared by every position.
+        let mut table = TranspositionTable::with_replacement(0, Replacement::DepthPreferred);
+        let positions = positions();
+        for (board, _) in &positions[..4] {
+            table.store(board, 0, entry(None, 0, 5, Bound::Exact));
+        }
+        // Shallower than everything in the bucket.
+        table.store(&positions[4].0, 0, entry(None, 0, 3, Bound::Exact));
+        assert_eq!(table.probe(&positions[4].0, 0), None);
+        // An exact entry replaces a deeper one of the same position, a bound does not.
+        table.store(&positions[0].0, 0, entry(None, 7, 2, Bound::Exact));
+        assert_eq!(table.probe(&positions[0].0, 0).unwrap().score, 7);
+        table.store(&positions[0].0, 0, entry(None, 9, 1, Bound::Lower));
+        assert_eq!(table.probe(&positions[0].0, 0).unwrap().score, 7);
+
+        // Deeper entries go in over the shallowest one.
+        table.store(&positions[5].0, 0, entry(None, 0, 6, Bound::Exact));
+        assert!(table.probe(&positions[5].0, 0).is_some());
+        assert_eq!(table.probe(&positions[0].0, 0), None);
+
+        // Entries of an older search give way to anything.
+        table.new_search();
+        table.store(&positions[6].0, 0, entry(None, 0, 1, Bound::Upper));
+        assert!(table.probe(&positions[6].0, 0).is_some());
+    }
+
+    #[test]
+    fn always_replace() {
+        let mut table = TranspositionTable::with_replacement(0, Replacement::AlwaysReplace);
+        let positions = positions();
+        for (board, _) in &positions[..4] {
+            table.store(board, 0, entry(None, 0, 5, Bound::Exact));
+        }
+        table.store(&positions[4].0, 0, entry(None, 0, 1, Bound::Exact));
+        assert!(table.probe(&positions[4].0, 0).is_some());
+        let kept = positions[..4]
+            .iter()
+            .filter(|(board, _)| table.probe(board, 0).is_some())
+            .count();
+        assert_eq!(kept, 3);
+    }
+
+    #[test]
+    fn hashfull() {
+        let mut table = TranspositionTable::new(0);
+        assert_eq!(table.hashfull(), 0);
+        let positions = positions();
+        for (board, _) in &positions[..2] {
+            table.store(board, 0, entry(None, 0, 1, Bound::Exact));
+        }
+        assert_eq!(table.hashfull(), 500);
+        table.new_search();
+        assert_eq!(table.hashfull(), 0);
+    }
+}
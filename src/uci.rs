@@ -0,0 +1,423 @@
+use board_evaluation::board_evaluator::BoardEvaluator;
+use board_representation::chess_board::ChessBoard;
+use board_representation::chess_piece::Color;
+use moves_generation::chess_move::Move;
+use moves_generation::valid_moves::all_valid_moves;
+use search::pruning::Pruning;
+use search::searcher::{SearchLimits, SearchResult, Searcher, mate_in};
+use std::io::{BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use transposition_table::transposition_table::{DEFAULT_SIZE_MB, TranspositionTable};
+
+const ENGINE_NAME: &str = "Scamorza";
+const ENGINE_AUTHOR: &str = "francescoo22";
+
+/// Moves the remaining clock time is split over when the GUI does not send `movestogo`.
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+
+/// Bounds of the `Hash` option, the size of the transposition table in megabytes.
+const MIN_HASH_MB: usize = 1;
+const MAX_HASH_MB: usize = 1024;
+
+/// Parameters of the `go` command. Time values are in milliseconds on the wire.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct GoParams {
+    pub depth: Option<u8>,
+    pub nodes: Option<u64>,
+    pub move_time: Option<Duration>,
+    pub white_time: Option<Duration>,
+    pub black_time: Option<Duration>,
+    pub white_increment: Option<Duration>,
+    pub black_increment: Option<Duration>,
+    pub moves_to_go: Option<u32>,
+    /// `go infinite` and `go ponder`: `bestmove` is only sent after `stop`.
+    pub infinite: bool,
+}
+
+impl GoParams {
+    pub fn parse(tokens: &[&str]) -> Self {
+        fn millis(value: Option<&&str>) -> Option<Duration> {
+            value
+                .and_then(|v| v.parse::<i64>().ok())
+                .map(|ms| Duration::from_millis(ms.max(0) as u64))
+        }
+
+        let mut params = GoParams::default();
+        let mut i = 0;
+        while i < tokens.len() {
+            let value = tokens.get(i + 1);
+            match tokens[i] {
+                "depth" => params.depth = value.and_then(|v| v.parse().ok()),
+                "nodes" => params.nodes = value.and_then(|v| v.parse().ok()),
+                "movetime" => params.move_time = millis(value),
+                "wtime" => params.white_time = millis(value),
+                "btime" => params.black_time = millis(value),
+                "winc" => params.white_increment = millis(value),
+                "binc" => params.black_increment = millis(value),
+                "movestogo" => params.moves_to_go = value.and_then(|v| v.parse().ok()),
+                "infinite" | "ponder" => {
+                    params.infinite = true;
+                    i += 1;
+                    continue;
+                }
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            }
+            i += 2;
+        }
+        params
+    }
+
+    /// Translates the parameters into search limits for `color`, the side to move.
+    /// Without `movetime`, the remaining clock time is split over the moves to go.
+    pub fn search_limits(&self, color: Color) -> SearchLimits {
+        let (time_left, increment) = match color {
+            Color::White => (self.white_time, self.white_increment),
+            Color::Black => (self.black_time, self.black_increment),
+        };
+        let time = self.move_time.or_else(|| {
+            time_left.map(|time_left| {
+                let moves_to_go = self.moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+                let budget = time_left / moves_to_go + increment.unwrap_or_default() / 2;
+                budget.min(time_left / 2)
+            })
+        });
+        SearchLimits {
+            depth: self.depth,
+            nodes: self.nodes,
+            time,
+        }
+    }
+}
+
+/// A UCI front-end that reads commands line by line and writes its answers to `W`.
+///
+/// The search runs on its own thread, so `isready` and `stop` are answered while searching.
+pub struct UciEngine<W: Write + Send + 'static> {
+    board: ChessBoard,
+    evaluator: BoardEvaluator,
+    output: Arc<Mutex<W>>,
+    stop: Arc<AtomicBool>,
+    /// Lent to the search thread while searching and given back when it ends.
+    transposition_table: Option<TranspositionTable>,
+    /// The `Hash` option, to allocate a new table when a failed search loses it.
+    hash_mb: usize,
+    /// Gives the table back, or `None` when the search failed.
+    search_thread: Option<JoinHandle<Option<TranspositionTable>>>,
+    search_infinite: bool,
+    pruning: Pruning,
+    /// `UCI_Chess960`: castling moves are written as the king taking its own rook.
+    chess960: bool,
+}
+
+impl<W: Write + Send + 'static> UciEngine<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            board: ChessBoard::default(),
+            evaluator: BoardEvaluator {
+                eval_material: true,
+                eval_doubled_pawns: true,
+                eval_isolated_pawns: true,
+                eval_blocked_pawns: true,
+            },
+            output: Arc::new(Mutex::new(output)),
+            stop: Arc::new(AtomicBool::new(false)),
+            transposition_table: Some(TranspositionTable::new(DEFAULT_SIZE_MB)),
+            hash_mb: DEFAULT_SIZE_MB,
+            search_thread: None,
+            search_infinite: false,
+            pruning: Pruning::default(),
+            chess960: false,
+        }
+    }
+
+    /// Processes commands until `quit` or the end of the input.
+    /// At the end of the input, a running search is allowed to finish unless it is infinite.
+    pub fn run<R: BufRead>(&mut self, input: R) {
+        for line in input.lines() {
+            let Ok(line) = line else { break };
+            if !self.handle_command(&line) {
+                return;
+            }
+        }
+        if self.search_infinite {
+            self.stop.store(true, Ordering::Relaxed);
+        }
+        self.wait_for_search();
+    }
+
+    /// Handles a single command, returns `false` when the engine should quit.
+    pub fn handle_command(&mut self, line: &str) -> bool {
+        let tokens = line.split_whitespace().collect::<Vec<&str>>();
+        let Some((command, arguments)) = tokens.split_first() else {
+            return true;
+        };
+        match *command {
+            "uci" => self.identify(),
+            "isready" => self.send("readyok"),
+            "ucinewgame" => {
+                self.stop_search();
+                self.board = ChessBoard::default();
+                self.transposition_table().clear();
+            }
+            "setoption" => self.set_option(arguments),
+            "position" => self.set_position(arguments),
+            "go" => self.go(GoParams::parse(arguments)),
+            "stop" => self.stop_search(),
+            "quit" => {
+                self.stop_search();
+                return false;
+            }
+            // Unknown commands are ignored, as required by the protocol.
+            _ => {}
+        }
+        true
+    }
+
+    /// Waits for the search thread and returns the output the engine has written to.
+    pub fn into_output(mut self) -> W {
+        self.wait_for_search();
+        match Arc::try_unwrap(self.output) {
+            Ok(output) => output.into_inner().unwrap(),
+            Err(_) => unreachable!("The search thread has been joined"),
+        }
+    }
+
+    fn send(&self, line: &str) {
+        send(&self.output, line);
+    }
+
+    fn identify(&self) {
+        self.send(&format!("id name {}", ENGINE_NAME));
+        self.send(&format!("id author {}", ENGINE_AUTHOR));
+        for (name, value) in self.check_options() {
+            self.send(&format!(
+                "option name {} type check default {}",
+                name, value
+            ));
+        }
+        self.send(&format!(
+            "option name Hash type spin default {} min {} max {}",
+            DEFAULT_SIZE_MB, MIN_HASH_MB, MAX_HASH_MB
+        ));
+        self.send("uciok");
+    }
+
+    fn check_options(&self) -> [(&'static str, bool); 10] {
+        [
+            ("DoubledPawns", self.evaluator.eval_doubled_pawns),
+            ("IsolatedPawns", self.evaluator.eval_isolated_pawns),
+            ("BlockedPawns", self.evaluator.eval_blocked_pawns),
+            ("NullMovePruning", self.pruning.null_move),
+            ("LateMoveReductions", self.pruning.late_move_reductions),
+            ("ReverseFutilityPruning", self.pruning.reverse_futility),
+            ("FutilityPruning", self.pruning.futility),
+            ("Razoring", self.pruning.razoring),
+            ("LateMovePruning", self.pruning.late_move_pruning),
+            ("UCI_Chess960", self.chess960),
+        ]
+    }
+
+    fn set_option(&mut self, arguments: &[&str]) {
+        // setoption name <name> [value <value>], where the name may contain spaces.
+        let value_index = arguments
+            .iter()
+            .position(|token| *token == "value")
+            .unwrap_or(arguments.len());
+        if arguments.first() != Some(&"name") || value_index < 2 {
+            self.send("info string Invalid setoption command");
+            return;
+        }
+        let name = arguments[1..value_index].join(" ");
+        let value = arguments.get(value_index + 1..).unwrap_or(&[]).join(" ");
+
+        if name.eq_ignore_ascii_case("hash") {
+            match value.parse::<usize>() {
+                Ok(size_mb) if (MIN_HASH_MB..=MAX_HASH_MB).contains(&size_mb) => {
+                    self.stop_search();
+                    self.transposition_table().resize(size_mb);
+                    self.hash_mb = size_mb;
+                }
+                _ => self.send(&format!("info string Invalid value {} for {}", value, name)),
+            }
+            return;
+        }
+        let flag = match name.to_lowercase().as_str() {
+            "doubledpawns" => &mut self.evaluator.eval_doubled_pawns,
+            "isolatedpawns" => &mut self.evaluator.eval_isolated_pawns,
+            "blockedpawns" => &mut self.evaluator.eval_blocked_pawns,
+            "nullmovepruning" => &mut self.pruning.null_move,
+            "latemovereductions" => &mut self.pruning.late_move_reductions,
+            "reversefutilitypruning" => &mut self.pruning.reverse_futility,
+            "futilitypruning" => &mut self.pruning.futility,
+            "razoring" => &mut self.pruning.razoring,
+            "latemovepruning" => &mut self.pruning.late_move_pruning,
+            "uci_chess960" => &mut self.chess960,
+            _ => {
+                self.send(&format!("info string Unknown option {}", name));
+                return;
+            }
+        };
+        match value.as_str() {
+            "true" => *flag = true,
+            "false" => *flag = false,
+            _ => self.send(&format!("info string Invalid value {} for {}", value, name)),
+        }
+    }
+
+    fn set_position(&mut self, arguments: &[&str]) {
+        let moves_index = arguments
+            .iter()
+            .position(|token| *token == "moves")
+            .unwrap_or(arguments.len());
+        let mut board = match arguments.first() {
+            Some(&"startpos") => ChessBoard::default(),
+            Some(&"fen") => {
+                let fen = arguments[1..moves_index].join(" ");
+                let board = if self.chess960 {
+                    ChessBoard::from_chess960_fen_validated(&fen)
+                } else {
+                    ChessBoard::from_fen_validated(&fen)
+                };
+                match board {
+                    Ok(board) => board,
+                    Err(error) => {
+                        self.send(&format!("info string {}", error));
+                        return;
+                    }
+                }
+            }
+            _ => {
+                self.send("info string Expected 'startpos' or 'fen'");
+                return;
+            }
+        };
+
+        for uci in arguments.iter().skip(moves_index + 1) {
+            match find_legal_move(&board, uci) {
+                Some(mov) => mov.move_piece(&mut board),
+                None => {
+                    self.send(&format!("info string Illegal move {}", uci));
+                    break;
+                }
+            }
+        }
+        self.board = board;
+    }
+
+    fn go(&mut self, params: GoParams) {
+        self.stop_search();
+        self.stop.store(false, Ordering::Relaxed);
+        self.search_infinite = params.infinite;
+
+        let board = self.board;
+        let evaluator = self.evaluator;
+        let pruning = self.pruning;
+        let output = Arc::clone(&self.output);
+        let stop = Arc::clone(&self.stop);
+        let chess960 = self.chess960;
+        let limits = params.search_limits(board.current_turn());
+        let transposition_table = self.transposition_table.take().unwrap();
+        self.search_thread = Some(thread::spawn(move || {
+            let mut searcher = Searcher::with_transposition_table(
+                evaluator,
+                limits,
+                Arc::clone(&stop),
+                transposition_table,
+            )
+            .with_pruning(pruning);
+            // The GUI waits for a best move whatever happens to the search.
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                searcher.search(&board, |iteration| {
+                    send(&output, &format_info(iteration, chess960));
+                })
+            }));
+            let Ok(result) = result else {
+                send(&output, "info string Search failed");
+                send(&output, "bestmove 0000");
+                return None;
+            };
+            while params.infinite && !stop.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+            let best_move = match result.best_move {
+                Some(mov) => uci_string(mov, chess960),
+                None => "0000".to_string(),
+            };
+            send(&output, &format!("bestmove {}", best_move));
+            Some(searcher.into_transposition_table())
+        }));
+    }
+
+    fn stop_search(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.wait_for_search();
+    }
+
+    fn wait_for_search(&mut self) {
+        if let Some(handle) = self.search_thread.take() {
+            let transposition_table = handle.join().ok().flatten();
+            self.transposition_table =
+                Some(transposition_table.unwrap_or_else(|| TranspositionTable::new(self.hash_mb)));
+        }
+        self.search_infinite = false;
+    }
+
+    /// The transposition table, once the search has given it back.
+    fn transposition_table(&mut self) -> &mut TranspositionTable {
+        self.wait_for_search();
+        self.transposition_table
+            .as_mut()
+            .expect("The table is back when no search is running")
+    }
+}
+
+fn send<W: Write>(output: &Mutex<W>, line: &str) {
+    // A search that panicked while writing leaves the lock poisoned, the output is still fine.
+    let mut output = output.lock().unwrap_or_else(|error| error.into_inner());
+    // A closed output means the GUI is gone, there is nobody left to report to.
+    let _ = writeln!(output, "{}", line).and_then(|_| output.flush());
+}
+
+fn find_legal_move(board: &ChessBoard, uci: &str) -> Option<Move> {
+    let mov = Move::from_uci_string(uci, board).ok()?;
+    all_valid_moves(board)
+        .into_iter()
+        .find(|legal_move| *legal_move == mov)
+}
+
+fn uci_string(mov: Move, chess960: bool) -> String {
+    if chess960 {
+        mov.to_chess960_uci_string()
+    } else {
+        mov.to_uci_string()
+    }
+}
+
+fn format_info(result: &SearchResult, chess960: bool) -> String {
+    let score = match mate_in(result.score) {
+        Some(moves) => format!("mate {}", moves),
+        None => format!("cp {}", result.score),
+    };
+    let pv = result
+        .pv
+        .iter()
+        .map(|mov| uci_string(*mov, chess960))
+        .collect::<Vec<String>>()
+        .join(" ");
+    format!(
+        "info depth {} score {} nodes {} time {} hashfull {} pv {}",
+        result.depth,
+        score,
+        result.nodes,
+        result.elapsed.as_millis(),
+        result.hashfull,
+        pv
+    )
+}
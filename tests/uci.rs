@@ -0,0 +1,266 @@
+#[cfg(test)]
+mod uci {
+    use board_representation::chess_piece::Color;
+    use chess_engine::uci::{GoParams, UciEngine};
+    use std::time::Duration;
+
+    fn run_script(script: &str) -> Vec<String> {
+        let mut engine = UciEngine::new(Vec::new());
+        engine.run(script.as_bytes());
+        let output = engine.into_output();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    fn best_move(output: &[String]) -> &str {
+        let line = output
+            .iter()
+            .find(|line| line.starts_with("bestmove"))
+            .expect("No bestmove in output");
+        line.split_whitespace().nth(1).unwrap()
+    }
+
+    #[test]
+    fn uci_handshake() {
+        let output = run_script("uci\n");
+        assert_eq!(output[0], "id name Scamorza");
+        assert!(output[1].starts_with("id author "));
+        assert!(output.iter().any(|line| line.starts_with("option name ")));
+        assert_eq!(output.last().unwrap(), "uciok");
+    }
+
+    #[test]
+    fn isready() {
+        assert_eq!(run_script("isready\n"), vec!["readyok"]);
+    }
+
+    #[test]
+    fn unknown_commands_are_ignored() {
+        assert_eq!(run_script("\nfoo bar\nisready\n"), vec!["readyok"]);
+    }
+
+    #[test]
+    fn go_from_start_position() {
+        let output = run_script("ucinewgame\nposition startpos\ngo depth 1\n");
+        assert!(
+            output
+                .iter()
+                .any(|line| line.starts_with("info depth 1 score cp "))
+        );
+        assert!(output.iter().any(|line| line.contains(" hashfull ")));
+        let legal_first_moves = [
+            "a2a3", "a2a4", "b2b3", "b2b4", "c2c3", "c2c4", "d2d3", "d2d4", "e2e3", "e2e4", "f2f3",
+            "f2f4", "g2g3", "g2g4", "h2h3", "h2h4", "b1a3", "b1c3", "g1f3", "g1h3",
+        ];
+        assert!(legal_first_moves.contains(&best_move(&output)));
+    }
+
+    #[test]
+    fn takes_hanging_queen() {
+        let output = run_script("position fen 4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1\ngo depth 2\n");
+        assert_eq!(best_move(&output), "e4d5");
+    }
+
+    #[test]
+    fn position_with_moves() {
+        // 1. e4 e5 2. Nf3 Qh4 hangs the queen to the knight.
+        let output = run_script("position startpos moves e2e4 e7e5 g1f3 d8h4\ngo depth 2\n");
+        assert_eq!(best_move(&output), "f3h4");
+    }
+
+    #[test]
+    fn reports_mate_score() {
+        let output = run_script("position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1\ngo depth 3\n");
+        assert!(output.iter().any(|line| line.contains("score mate 1 ")));
+        assert_eq!(best_move(&output), "a1a8");
+    }
+
+    #[test]
+    fn go_with_clock() {
+        let output = run_script("position startpos\ngo wtime 1000 btime 1000\n");
+        assert!(output.iter().any(|line| line.starts_with("bestmove ")));
+    }
+
+    #[test]
+    fn illegal_move_is_reported() {
+        let output = run_script("position startpos moves e2e5\n");
+        assert_eq!(output, vec!["info string Illegal move e2e5"]);
+    }
+
+    #[test]
+    fn invalid_fen_is_reported() {
+        let output = run_script("position fen 8/8/8/8/8/8/8/8 x - - 0 1\nisready\n");
+        assert_eq!(
+            output,
+            vec![
+                "info string Invalid FEN side to move at position 16, expected 'w' or 'b', found 'x'",
+                "readyok"
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_position_is_reported() {
+        let output = run_script("position fen 8/8/8/8/8/8/8/8 w - - 0 1\ngo depth 1\n");
+        assert_eq!(
+            output[0],
+            "info string Invalid FEN, Invalid position: White has no king, Black has no king"
+        );
+        // The previous position is kept.
+        assert_eq!(best_move(&output).len(), 4);
+    }
+
+    #[test]
+    fn no_legal_moves() {
+        let output = run_script("position fen 7k/6Q1/6K1/8/8/8/8/8 b - - 0 1\ngo depth 2\n");
+        assert_eq!(best_move(&output), "0000");
+    }
+
+    #[test]
+    fn infinite_search_waits_for_stop() {
+        let output = run_script("position startpos\ngo infinite\nisready\nstop\n");
+        let readyok = output.iter().position(|line| line == "readyok").unwrap();
+        let bestmove = output
+            .iter()
+            .position(|line| line.starts_with("bestmove"))
+            .unwrap();
+        assert!(readyok < bestmove);
+    }
+
+    #[test]
+    fn quit_ends_the_session() {
+        assert_eq!(run_script("quit\nisready\n"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn setoption() {
+        let output = run_script(
+            "setoption name DoubledPawns value false\nsetoption name Contempt value 16\nisready\n",
+        );
+        assert_eq!(
+            output,
+            vec!["info string Unknown option Contempt", "readyok"]
+        );
+
+        let output = run_script(
+            "uci\nsetoption name Hash value 64\nsetoption name Hash value 0\nucinewgame\ngo depth 3\n",
+        );
+        assert!(
+            output.contains(&"option name Hash type spin default 16 min 1 max 1024".to_string())
+        );
+        assert!(output.contains(&"info string Invalid value 0 for Hash".to_string()));
+        assert!(output.last().unwrap().starts_with("bestmove "));
+    }
+
+    #[test]
+    fn pruning_options() {
+        let output = run_script("uci\n");
+        for name in [
+            "NullMovePruning",
+            "LateMoveReductions",
+            "ReverseFutilityPruning",
+            "FutilityPruning",
+            "Razoring",
+            "LateMovePruning",
+        ] {
+            assert!(output.contains(&format!("option name {} type check default true", name)));
+        }
+
+        // The mate is found with the pruning off as well.
+        let output = run_script(
+            "setoption name NullMovePruning value false\nsetoption name Razoring value false\n\
+             setoption name LateMoveReductions value false\nsetoption name FutilityPruning value maybe\n\
+             position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1\ngo depth 3\n",
+        );
+        assert_eq!(
+            output[0],
+            "info string Invalid value maybe for FutilityPruning"
+        );
+        assert_eq!(best_move(&output), "a1a8");
+    }
+
+    #[test]
+    fn chess960_castling() {
+        // Only castling queenside mates, the king goes to c1 and the rook to d1.
+        let position = "position fen 2rkr3/2p1p3/8/8/8/8/8/RK6 w A - 0 1\ngo depth 2\n";
+        let output = run_script(&format!("uci\n{}", position));
+        assert!(output.contains(&"option name UCI_Chess960 type check default false".to_string()));
+        assert_eq!(best_move(&output), "b1c1");
+
+        let output = run_script(&format!(
+            "setoption name UCI_Chess960 value true\n{}",
+            position
+        ));
+        assert_eq!(best_move(&output), "b1a1");
+
+        // Without the option, KQkq needs the king on the e-file.
+        let position = "position fen r4k1r/8/8/8/8/8/8/R4K1R w KQkq - 0 1\nisready\n";
+        let output = run_script(position);
+        assert_eq!(
+            output[0],
+            "info string Invalid FEN, Invalid position: castling right 'K' without the king and \
+             rook on their starting squares, castling right 'Q' without the king and rook on \
+             their starting squares, castling right 'k' without the king and rook on their \
+             starting squares, castling right 'q' without the king and rook on their starting \
+             squares"
+        );
+        let output = run_script(&format!(
+            "setoption name UCI_Chess960 value true\n{}",
+            position
+        ));
+        assert_eq!(output, vec!["readyok"]);
+
+        // Castling is accepted in both forms, whatever the option.
+        let output = run_script(
+            "position fen r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1 moves e1a1 e8g8\nisready\n",
+        );
+        assert_eq!(output, vec!["readyok"]);
+    }
+
+    #[test]
+    fn parse_go_params() {
+        let params = GoParams::parse(&[
+            "wtime",
+            "1000",
+            "btime",
+            "2000",
+            "winc",
+            "10",
+            "binc",
+            "20",
+            "movestogo",
+            "5",
+        ]);
+        assert_eq!(params.white_time, Some(Duration::from_millis(1000)));
+        assert_eq!(params.black_time, Some(Duration::from_millis(2000)));
+        assert_eq!(params.white_increment, Some(Duration::from_millis(10)));
+        assert_eq!(params.black_increment, Some(Duration::from_millis(20)));
+        assert_eq!(params.moves_to_go, Some(5));
+        assert!(!params.infinite);
+
+        let params = GoParams::parse(&["infinite"]);
+        assert!(params.infinite);
+
+        let params = GoParams::parse(&["depth", "4", "nodes", "1000", "movetime", "50"]);
+        assert_eq!(params.depth, Some(4));
+        assert_eq!(params.nodes, Some(1000));
+        assert_eq!(params.move_time, Some(Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn search_limits() {
+        let params = GoParams::parse(&["wtime", "60000", "btime", "1000", "binc", "3000"]);
+        let limits = params.search_limits(Color::White);
+        assert_eq!(limits.time, Some(Duration::from_millis(2000)));
+        let limits = params.search_limits(Color::Black);
+        assert_eq!(limits.time, Some(Duration::from_millis(500)));
+
+        let params = GoParams::parse(&["movetime", "100", "wtime", "60000", "depth", "3"]);
+        let limits = params.search_limits(Color::White);
+        assert_eq!(limits.time, Some(Duration::from_millis(100)));
+        assert_eq!(limits.depth, Some(3));
+    }
+}